@@ -6,31 +6,11 @@ const NB_BITS_TO_COMPUTE: usize = 10000;
 
 /// This computes the bits of sqrt_2 in a biguint
 ///
-/// This does so bit by bit, shifting and comparing to a odd power of 2
-/// Surely not the optimal algorithm
+/// The integer square root of 2^(2n + 1) is the integer made of the first
+/// n + 1 bits of sqrt_2
 fn main() {
-    let mut two = BigUint::new(2);
-    let mut sqrt_2_approx = BigUint::new(1);
-    let mut two_approx =
-        BigUint::new(1).with_capacity((NB_BITS_TO_COMPUTE + 1) * (NB_BITS_TO_COMPUTE + 1));
-
-    for _ in 0..NB_BITS_TO_COMPUTE / 2 {
-        two <<= 4;
-        sqrt_2_approx <<= 2;
-        two_approx <<= 4;
-
-        while two_approx < two {
-            two_approx += &sqrt_2_approx;
-            sqrt_2_approx += 1;
-            two_approx += &sqrt_2_approx;
-        }
-
-        if two_approx >= two {
-            two_approx -= &sqrt_2_approx;
-            sqrt_2_approx -= 1;
-            two_approx -= &sqrt_2_approx;
-        }
-    }
+    let two = BigUint::new(1) << (2 * NB_BITS_TO_COMPUTE + 1);
+    let sqrt_2_approx = two.isqrt();
 
     // We check that we haven't done an oopsie daisy
     // sqrt_2_appprox should be the closest integer below the square
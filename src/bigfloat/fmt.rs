@@ -1,15 +1,110 @@
-use crate::BigInt;
+use crate::biguint::fmt::exp;
+use crate::biguint::ops::to_radix;
+use crate::traits::{Digit, Pow};
+use crate::BigFloat;
+use crate::BigUint;
 
-use crate::traits::Digit;
+impl<T: Digit> BigFloat<T> {
+    /// Writes the exact value in scientific notation: with a negative scale,
+    /// int * 2^-b is int * 5^b * 10^-b
+    fn scientific(&self, f: &mut std::fmt::Formatter<'_>, upper: bool) -> std::fmt::Result {
+        let is_nonnegative = !self.int.is_sign_negative();
+        let bits = T::NB_BITS * self.scale.unsigned_abs();
+        if self.scale >= 0 {
+            exp(f, is_nonnegative, &(&self.int.uint << bits), 0, upper)
+        } else {
+            let mantissa = &self.int.uint * BigUint::<T>::from(5u32).pow(bits);
+            exp(f, is_nonnegative, &mantissa, -(bits as isize), upper)
+        }
+    }
+}
 
-use crate::BigFloat;
+impl<T: Digit> BigFloat<T> {
+    /// Writes the exact value in positional notation, or rounded half to
+    /// even if the formatter has a precision
+    fn fixed(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let is_nonnegative = !self.int.is_sign_negative();
+        let bits = T::NB_BITS * self.scale.unsigned_abs();
+        let (mantissa, nb_fraction_digits) = match self.scale >= 0 {
+            true => (&self.int.uint << bits, 0),
+            false => (&self.int.uint * BigUint::<T>::from(5u32).pow(bits), bits),
+        };
+
+        let mut digits = to_radix(&mantissa, 10).into_bytes();
+        if digits.len() <= nb_fraction_digits {
+            let nb_zeros = nb_fraction_digits + 1 - digits.len();
+            digits.splice(0..0, std::iter::repeat_n(b'0', nb_zeros));
+        }
+        let mut nb_integer_digits = digits.len() - nb_fraction_digits;
+
+        match f.precision() {
+            Some(precision) if precision < nb_fraction_digits => {
+                let (kept, dropped) = digits.split_at(nb_integer_digits + precision);
+                let round_up = match dropped[0] {
+                    b'6'..=b'9' => true,
+                    b'5' => {
+                        dropped[1..].iter().any(|d| *d != b'0') || kept.last().unwrap() % 2 == 1
+                    }
+                    _ => false,
+                };
+                digits.truncate(nb_integer_digits + precision);
+                if round_up {
+                    match digits.iter().rposition(|d| *d != b'9') {
+                        Some(i) => {
+                            digits[i] += 1;
+                            digits[i + 1..].fill(b'0');
+                        }
+                        None => {
+                            digits.fill(b'0');
+                            digits.insert(0, b'1');
+                            nb_integer_digits += 1;
+                        }
+                    }
+                }
+            }
+            Some(precision) => digits.resize(nb_integer_digits + precision, b'0'),
+            None => {
+                let nb_digits = digits[nb_integer_digits..]
+                    .iter()
+                    .rposition(|d| *d != b'0')
+                    .map_or(0, |i| i + 1);
+                digits.truncate(nb_integer_digits + nb_digits);
+            }
+        }
+
+        let mut buf = String::with_capacity(digits.len() + 1);
+        buf.extend(digits[..nb_integer_digits].iter().map(|d| *d as char));
+        if digits.len() > nb_integer_digits {
+            buf.push('.');
+            buf.extend(digits[nb_integer_digits..].iter().map(|d| *d as char));
+        }
+        f.pad_integral(is_nonnegative, "", &buf)
+    }
+}
+
+impl<T: Digit> std::fmt::Display for BigFloat<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fixed(f)
+    }
+}
+
+impl<T: Digit> std::fmt::LowerExp for BigFloat<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.scientific(f, false)
+    }
+}
+impl<T: Digit> std::fmt::UpperExp for BigFloat<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.scientific(f, true)
+    }
+}
 
 impl<T: Digit> std::fmt::Binary for BigFloat<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if self.scale == 0 {
-            <BigInt<T> as std::fmt::Binary>::fmt(&self.int, f)
+            crate::bigint::fmt::bin(self.int.sign, &self.int.uint.val, f)
         } else if self.scale > 0 {
-            <BigInt<T> as std::fmt::Binary>::fmt(&self.int, f)?;
+            crate::bigint::fmt::bin(self.int.sign, &self.int.uint.val, f)?;
             let mut word = "".to_string();
             for _ in 0..T::NB_BITS {
                 word.push('0');
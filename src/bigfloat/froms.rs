@@ -1,7 +1,11 @@
 use crate::BigInt;
 use crate::BigUint;
 
-use crate::traits::Digit;
+use crate::biguint::ops::from_radix;
+use crate::errors::FromFloatError;
+use crate::errors::ParseError;
+use crate::rounding::{RoundingMode, F32, F64};
+use crate::traits::{Digit, Pow, RemDiv};
 
 use crate::BigFloat;
 
@@ -18,7 +22,11 @@ fn clean_uint<T: Digit>(uint: &mut BigUint<T>) -> isize {
 impl<T: Digit> From<BigInt<T>> for BigFloat<T> {
     fn from(mut val: BigInt<T>) -> Self {
         let scale = clean_uint(&mut val.uint);
-        Self { int: val, scale }
+        Self {
+            int: val,
+            scale,
+            precision: None,
+        }
     }
 }
 
@@ -28,6 +36,7 @@ impl<T: Digit> From<BigUint<T>> for BigFloat<T> {
         Self {
             int: val.into(),
             scale,
+            precision: None,
         }
     }
 }
@@ -38,8 +47,301 @@ impl<T: Digit> From<Vec<T>> for BigFloat<T> {
     }
 }
 
-impl<T: Digit> From<T> for BigFloat<T> {
-    fn from(val: T) -> Self {
-        Self::from(BigUint::new(val))
+/// Exact conversions from the primitive integers
+macro_rules! from_primitive {
+    ($($t:ty),*) => {$(
+        impl<T: Digit> From<$t> for BigFloat<T> {
+            fn from(val: $t) -> BigFloat<T> {
+                BigFloat::<T>::from(BigInt::<T>::from(val))
+            }
+        }
+    )*};
+}
+from_primitive!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl<T: Digit> BigFloat<T> {
+    /// mantissa * 2^exponent, with the precision of the float it comes from
+    fn from_float_parts(negative: bool, mantissa: u64, exponent: isize, precision: usize) -> Self {
+        let mut ret = BigFloat::from(mantissa).mul_pow2(exponent);
+        ret.int.sign = !negative || mantissa == 0;
+        ret.precision = Some(precision);
+        ret
+    }
+}
+
+/// Exact conversion, the result has a precision of 53 bits
+impl<T: Digit> TryFrom<f64> for BigFloat<T> {
+    type Error = FromFloatError<f64>;
+
+    fn try_from(f: f64) -> Result<BigFloat<T>, FromFloatError<f64>> {
+        if !f.is_finite() {
+            return Err(FromFloatError::NotNormal(f));
+        }
+
+        let bits = f.to_bits();
+        let fraction = bits & ((1 << 52) - 1);
+        let (mantissa, exponent) = match ((bits >> 52) & 0x7ff) as isize {
+            0 => (fraction, -1074),
+            biased => (fraction | (1 << 52), biased - 1075),
+        };
+        Ok(BigFloat::from_float_parts(
+            f.is_sign_negative(),
+            mantissa,
+            exponent,
+            53,
+        ))
+    }
+}
+
+/// Exact conversion, the result has a precision of 24 bits
+impl<T: Digit> TryFrom<f32> for BigFloat<T> {
+    type Error = FromFloatError<f32>;
+
+    fn try_from(f: f32) -> Result<BigFloat<T>, FromFloatError<f32>> {
+        if !f.is_finite() {
+            return Err(FromFloatError::NotNormal(f));
+        }
+
+        let bits = f.to_bits();
+        let fraction = (bits & ((1 << 23) - 1)) as u64;
+        let (mantissa, exponent) = match ((bits >> 23) & 0xff) as isize {
+            0 => (fraction, -149),
+            biased => (fraction | (1 << 23), biased - 150),
+        };
+        Ok(BigFloat::from_float_parts(
+            f.is_sign_negative(),
+            mantissa,
+            exponent,
+            24,
+        ))
+    }
+}
+
+impl<T: Digit> From<&BigFloat<T>> for f64 {
+    fn from(float: &BigFloat<T>) -> f64 {
+        float.to_f64_with_rounding(RoundingMode::NearestEven).0
+    }
+}
+
+impl<T: Digit> From<&BigFloat<T>> for f32 {
+    fn from(float: &BigFloat<T>) -> f32 {
+        let negative = float.int.is_sign_negative();
+        let exponent = float.scale * T::NB_BITS as isize;
+        let mode = RoundingMode::NearestEven;
+        let (bits, _) = F32.round(&float.int.uint, exponent, false, negative, mode);
+        f32::from_bits(bits as u32 | (negative as u32) << 31)
+    }
+}
+
+impl<T: Digit> BigFloat<T> {
+    /// Converts to the `f64` obtained by rounding with the given mode, and
+    /// tells whether the conversion is exact. Numbers too large for a `f64`
+    /// give an infinity, or the largest finite `f64` of the same sign when
+    /// rounding toward zero, or away from the infinity for `Floor` and `Ceil`.
+    /// Numbers too small give a zero or a subnormal `f64`.
+    ///
+    /// ```
+    /// use twibint::{BigFloat, RoundingMode};
+    ///
+    /// let n = BigFloat::<u64>::from(1u32).div(&BigFloat::from(3u32), 100).unwrap();
+    /// assert_eq!(n.to_f64_with_rounding(RoundingMode::NearestEven), (1.0 / 3.0, false));
+    /// assert!(n.to_f64_with_rounding(RoundingMode::Ceil).0 > 1.0 / 3.0);
+    /// ```
+    pub fn to_f64_with_rounding(&self, mode: RoundingMode) -> (f64, bool) {
+        let negative = self.int.is_sign_negative();
+        let exponent = self.scale * T::NB_BITS as isize;
+        let (bits, exact) = F64.round(&self.int.uint, exponent, false, negative, mode);
+        (f64::from_bits(bits | (negative as u64) << 63), exact)
+    }
+}
+
+/// A decimal number as read from a string: its sign, the integer made of
+/// its digits, the power of ten to apply to it, and its number of
+/// significant digits
+struct Decimal<T: Digit> {
+    negative: bool,
+    mantissa: BigUint<T>,
+    exponent: isize,
+    nb_digits: usize,
+}
+
+fn unexpected(s: &str, position: usize) -> ParseError {
+    ParseError::UnexpectedCharacter {
+        character: s[position..].chars().next().unwrap(),
+        position,
+    }
+}
+
+/// The largest power of ten, in absolute value, that a decimal string can
+/// have in its exponent. Beyond it the exact power of five would be too large
+/// to compute, but a zero can have any exponent.
+const MAX_DECIMAL_EXPONENT: isize = 1_000_000;
+
+/// Reads `[+-]digits[.digits][(e|E)[+-]digits]`, where either the integer
+/// or the fractional part can be empty, but not both
+fn parse_decimal<T: Digit>(s: &str) -> Result<Decimal<T>, ParseError> {
+    let bytes = s.as_bytes();
+    let mut position = 0;
+
+    let negative = bytes.first() == Some(&b'-');
+    if matches!(bytes.first(), Some(b'-' | b'+')) {
+        position += 1;
+    }
+
+    let mut values = Vec::with_capacity(bytes.len());
+    let mut nb_fraction_digits = 0;
+    let mut dot = false;
+    while let Some(c) = bytes.get(position) {
+        match c {
+            b'0'..=b'9' => {
+                values.push(c - b'0');
+                nb_fraction_digits += dot as isize;
+            }
+            b'.' if !dot => dot = true,
+            _ => break,
+        }
+        position += 1;
+    }
+    // Without any digit, blame the character that should have been one
+    if values.is_empty() {
+        return Err(match position < bytes.len() {
+            true => unexpected(s, position),
+            false => ParseError::NoDigits,
+        });
+    }
+    let zero = values.iter().all(|v| *v == 0);
+
+    let mut exponent: isize = 0;
+    if let Some(b'e' | b'E') = bytes.get(position) {
+        let e_position = position;
+        position += 1;
+        let exponent_negative = bytes.get(position) == Some(&b'-');
+        if matches!(bytes.get(position), Some(b'-' | b'+')) {
+            position += 1;
+        }
+
+        let start = position;
+        while let Some(c @ b'0'..=b'9') = bytes.get(position) {
+            if !zero {
+                exponent = exponent * 10 + (c - b'0') as isize;
+                if exponent > MAX_DECIMAL_EXPONENT {
+                    return Err(unexpected(s, position));
+                }
+            }
+            position += 1;
+        }
+        if position == start {
+            return Err(unexpected(s, e_position));
+        }
+        if exponent_negative {
+            exponent = -exponent;
+        }
+    }
+    if position < bytes.len() {
+        return Err(unexpected(s, position));
+    }
+
+    let leading_zeros = values.iter().take_while(|v| **v == 0).count();
+    Ok(Decimal {
+        negative,
+        mantissa: from_radix(&values, 10),
+        exponent: exponent.saturating_sub(nb_fraction_digits),
+        nb_digits: values.len() - leading_zeros,
+    })
+}
+
+impl<T: Digit> Decimal<T> {
+    /// The value rounded to `precision` bits, or exact if precision is None
+    /// and the value is representable
+    fn into_float(self, precision: Option<usize>) -> BigFloat<T> {
+        if self.mantissa.is_zero() {
+            let mut ret = BigFloat::from(self.mantissa);
+            if let Some(precision) = precision {
+                ret.set_precision(precision);
+            }
+            return ret;
+        }
+        if self.exponent >= 0 {
+            let five_pow = BigUint::<T>::from(5u32).pow(self.exponent as usize);
+            let mut ret = BigFloat::from(self.mantissa * five_pow).mul_pow2(self.exponent);
+            ret.int.sign = !self.negative || ret.int.uint.is_zero();
+            if let Some(precision) = precision {
+                ret.set_precision(precision);
+            }
+            return ret;
+        }
+
+        let five_pow = BigUint::<T>::from(5u32).pow((-self.exponent) as usize);
+        let precision = match precision {
+            Some(precision) => precision,
+            None => {
+                let (q, r) = self.mantissa.rem_div(&five_pow).unwrap();
+                if r.is_zero() {
+                    let mut ret = BigFloat::from(q).mul_pow2(self.exponent);
+                    ret.int.sign = !self.negative || ret.int.uint.is_zero();
+                    return ret;
+                }
+                decimal_precision(self.nb_digits)
+            }
+        };
+
+        // Scale the mantissa so that the quotient has two more bits than the
+        // precision, the remainder then only decides on the rounding of ties
+        let shift = (precision + 2 + five_pow.nb_bits()).saturating_sub(self.mantissa.nb_bits());
+        let (q, r) = (&self.mantissa << shift).rem_div(&five_pow).unwrap();
+        let mut ret = BigFloat::from(q).mul_pow2(self.exponent - shift as isize);
+        ret.int.sign = !self.negative || ret.int.uint.is_zero();
+        ret.round_bits(precision, RoundingMode::NearestEven, !r.is_zero());
+        ret.precision = Some(precision);
+        ret
+    }
+}
+
+/// Enough bits to tell apart all the decimal numbers with that many
+/// significant digits, and at least the precision of an f64
+fn decimal_precision(nb_digits: usize) -> usize {
+    ((nb_digits * 3322).div_ceil(1000) + 1).max(53)
+}
+
+impl<T: Digit> BigFloat<T> {
+    /// Reads a decimal number such as `-12.5e-3`, rounded to nearest, ties
+    /// to even, to `precision` bits. Unless the number is zero, its exponent
+    /// cannot be larger than a million in absolute value.
+    ///
+    /// ```
+    /// use twibint::{BigFloat, ParseError};
+    ///
+    /// let n = BigFloat::<u64>::from_str_with_precision("0.1", 4).unwrap();
+    /// assert_eq!(n, BigFloat::from(13u32) >> 7);
+    /// assert_eq!(n.precision(), Some(4));
+    ///
+    /// let e = BigFloat::<u64>::from_str_with_precision("1.2.3", 4).unwrap_err();
+    /// assert_eq!(e, ParseError::UnexpectedCharacter { character: '.', position: 3 });
+    /// ```
+    pub fn from_str_with_precision(s: &str, precision: usize) -> Result<BigFloat<T>, ParseError> {
+        assert!(
+            precision > 0,
+            "A BigFloat needs at least one bit of precision"
+        );
+        Ok(parse_decimal(s)?.into_float(Some(precision)))
+    }
+}
+
+/// Reads a decimal number such as `-12.5e-3`. The result is exact if the
+/// number is representable, and otherwise rounded with enough bits to tell
+/// apart all the numbers with as many significant digits, and at least 53.
+/// Unless the number is zero, its exponent cannot be larger than a million in
+/// absolute value.
+impl<T: Digit> std::str::FromStr for BigFloat<T> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<BigFloat<T>, ParseError> {
+        Ok(parse_decimal(s)?.into_float(None))
+    }
+}
+
+impl<T: Digit> From<&str> for BigFloat<T> {
+    fn from(s: &str) -> BigFloat<T> {
+        <BigFloat<T> as std::str::FromStr>::from_str(s).unwrap()
     }
 }
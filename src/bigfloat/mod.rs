@@ -1,8 +1,10 @@
-//! Experimental implementation of a BigFloat type: a floating point signed
-//! number, able to represent a subset of rational numbers.
+//! Implementation of the BigFloat type: a floating point signed number,
+//! able to represent the rationals whose denominator is a power of two.
 //!
-//! These numbers are not meant to be approximations, and every operation
-//! must be implemented in a lossless manner
+//! A BigFloat without precision is exact, and every operation on it is
+//! lossless. A precision in bits makes operations round their result to
+//! that many significant bits.
+use crate::rounding::RoundingMode;
 use crate::BigInt;
 use crate::BigUint;
 use std::cmp::Ordering;
@@ -18,11 +20,28 @@ mod tests;
 
 /// The BigFloat type represents a signed floating point number.
 /// It is composed of a `BigInt` represeting the mantissa, and a scale
-/// specifying by how many digits it is supposed to be shifted
-#[derive(Clone, Debug, PartialEq, Eq)]
-pub(crate) struct BigFloat<T: Digit> {
+/// specifying by how many digits it is supposed to be shifted.
+///
+/// It also carries an optional precision in bits. Arithmetic operators
+/// round their result to nearest, ties to even, at the largest precision
+/// of their operands, and are exact when none of them has a precision,
+/// except for the division which panics then.
+///
+/// ```
+/// use twibint::BigFloat;
+///
+/// let a = BigFloat::<u64>::from(1u32).with_precision(4);
+/// let b = BigFloat::<u64>::from(1u32) >> 5;
+///
+/// assert_eq!(&a + &b, a);
+/// assert_eq!((&a + &b).precision(), Some(4));
+/// assert_eq!(BigFloat::from(1u32) + &b, BigFloat::from(33u32) >> 5);
+/// ```
+#[derive(Clone, Debug, Eq)]
+pub struct BigFloat<T: Digit> {
     pub(crate) int: BigInt<T>,
     pub(crate) scale: isize,
+    pub(crate) precision: Option<usize>,
 }
 
 impl<T: Digit> BigFloat<T> {
@@ -30,6 +49,111 @@ impl<T: Digit> BigFloat<T> {
         Self::from(BigUint::<T>::new(val))
     }
 
+    /// Returns the precision in bits, or None if the value is exact
+    ///
+    /// ```
+    /// use twibint::BigFloat;
+    ///
+    /// assert_eq!(BigFloat::<u64>::from(3u32).precision(), None);
+    /// assert_eq!(BigFloat::<u64>::try_from(0.5f64).unwrap().precision(), Some(53));
+    /// ```
+    #[inline]
+    pub fn precision(&self) -> Option<usize> {
+        self.precision
+    }
+
+    /// Sets the precision in bits, rounding the value to nearest, ties to even
+    ///
+    /// ```
+    /// use twibint::BigFloat;
+    ///
+    /// let n = BigFloat::<u64>::from(0b10110u32).with_precision(3);
+    /// assert_eq!(n, BigFloat::from(0b11000u32));
+    /// ```
+    pub fn with_precision(mut self, precision: usize) -> Self {
+        self.set_precision(precision);
+        self
+    }
+
+    /// Sets the precision in bits, rounding the value to nearest, ties to even
+    pub fn set_precision(&mut self, precision: usize) {
+        self.set_precision_with_rounding(precision, RoundingMode::NearestEven);
+    }
+
+    /// Sets the precision in bits, rounding the value with the given mode,
+    /// and tells whether the rounding is exact
+    ///
+    /// ```
+    /// use twibint::{BigFloat, RoundingMode};
+    ///
+    /// let mut n = BigFloat::<u64>::from(-0b10110i32);
+    /// assert!(!n.set_precision_with_rounding(3, RoundingMode::Ceil));
+    /// assert_eq!(n, BigFloat::from(-0b10100i32));
+    /// assert!(n.set_precision_with_rounding(3, RoundingMode::Floor));
+    /// ```
+    pub fn set_precision_with_rounding(&mut self, precision: usize, mode: RoundingMode) -> bool {
+        assert!(
+            precision > 0,
+            "A BigFloat needs at least one bit of precision"
+        );
+        let uint = &self.int.uint;
+        let exact = uint.nb_bits() - uint.trailing_zeros().unwrap_or(0) <= precision;
+        self.round_bits(precision, mode, false);
+        self.precision = Some(precision);
+        exact
+    }
+
+    /// Exponent of the highest set bit, None for zero
+    pub(crate) fn top_bit(&self) -> Option<isize> {
+        match self.int.uint.is_zero() {
+            true => None,
+            false => Some(self.scale * T::NB_BITS as isize + self.int.uint.nb_bits() as isize - 1),
+        }
+    }
+
+    /// Exponent of the lowest set bit, None for zero
+    pub(crate) fn low_bit(&self) -> Option<isize> {
+        let trailing_zeros = self.int.uint.trailing_zeros()?;
+        Some(self.scale * T::NB_BITS as isize + trailing_zeros as isize)
+    }
+
+    /// Rounds the magnitude to `precision` significant bits. `sticky` means
+    /// that the exact value is a bit above the current magnitude, by less
+    /// than its lowest bit.
+    pub(crate) fn round_bits(&mut self, precision: usize, mode: RoundingMode, sticky: bool) {
+        let nb_bits = self.int.uint.nb_bits();
+        if nb_bits <= precision {
+            if sticky && mode.round_up(self.int.is_sign_negative(), false, false, true) {
+                let unit = self.top_bit().unwrap() + 1 - precision as isize;
+                let unit = BigFloat::new(T::ONE).mul_pow2(unit);
+                self.add_assign(self.int.sign, unit.scale, &unit.int.uint.val);
+            }
+            return;
+        }
+
+        let shift = nb_bits - precision;
+        let uint = &self.int.uint;
+        let half = uint.bit(shift - 1);
+        let lower = sticky || uint.trailing_zeros().unwrap() < shift - 1;
+        let mut q = uint >> shift;
+        let odd = q.bit(0);
+        if mode.round_up(self.int.is_sign_negative(), odd, half, lower) {
+            q += T::ONE;
+        }
+
+        self.int.uint = q << (shift % T::NB_BITS);
+        self.scale += (shift / T::NB_BITS) as isize;
+        self.simplify();
+    }
+
+    /// Rounds to the own precision of self, if any
+    #[inline]
+    pub(crate) fn round_to_precision(&mut self) {
+        if let Some(precision) = self.precision {
+            self.round_bits(precision, RoundingMode::NearestEven, false);
+        }
+    }
+
     #[inline]
     pub(crate) fn with_capacity(mut self, capcity: usize) -> Self {
         self.int.uint.set_capacity(capcity);
@@ -40,6 +164,7 @@ impl<T: Digit> BigFloat<T> {
     pub fn copy_from(&mut self, other: &Self) {
         self.int.copy_from(&other.int);
         self.scale = other.scale;
+        self.precision = other.precision;
     }
 
     /// Remove zero-digits at the beginning
@@ -128,7 +253,7 @@ impl<T: Digit> BigFloat<T> {
             return;
         }
 
-        let one_half = BigFloat::from(T::ONE) >> 1;
+        let one_half = BigFloat::new(T::ONE) >> 1;
         let adjust = match one_half.float_unsigned_ord(self.scale, &self.int.uint.val[0..scale]) {
             Ordering::Less => true,
             _ => false,
@@ -165,10 +290,17 @@ impl<T: Digit> Default for BigFloat<T> {
         BigFloat {
             int: Default::default(),
             scale: 0,
+            precision: None,
         }
     }
 }
 
+impl<T: Digit> PartialEq for BigFloat<T> {
+    fn eq(&self, other: &BigFloat<T>) -> bool {
+        self.int == other.int && self.scale == other.scale
+    }
+}
+
 impl<T: Digit> std::hash::Hash for BigFloat<T> {
     fn hash<H>(&self, state: &mut H)
     where
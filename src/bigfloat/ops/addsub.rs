@@ -1,6 +1,7 @@
 use crate::biguint::ops::add_assign;
 use crate::biguint::ops::rsub_assign;
 use crate::biguint::ops::sub_assign;
+use crate::rounding::RoundingMode;
 use crate::traits::Digit;
 use crate::BigInt;
 use std::cmp::Ordering;
@@ -21,6 +22,7 @@ impl<T: Digit> BigFloat<T> {
                 uint,
                 sign: self.int.sign,
             },
+            precision: self.precision,
         }
     }
 
@@ -139,6 +141,15 @@ impl<T: Digit> BigFloat<T> {
     }
 
     pub(crate) fn add_assign(&mut self, other_sign: bool, other_scale: isize, other: &[T]) {
+        if self.int.uint.is_zero() {
+            self.int.uint.val.clear();
+            self.int.uint.val.extend_from_slice(other);
+            self.int.sign = other_sign || self.int.uint.is_zero();
+            self.scale = other_scale;
+            self.simplify();
+            return;
+        }
+
         if self.int.sign == other_sign {
             self.unsigned_add(other_scale, other);
             return;
@@ -163,6 +174,75 @@ impl<T: Digit> BigFloat<T> {
     pub(crate) fn sub_assign(&mut self, other_sign: bool, other_scale: isize, other: &[T]) {
         self.add_assign(!other_sign, other_scale, other);
     }
+
+    /// If a nonzero number of highest bit `other_top` is too small to change
+    /// anything but the rounding of its sum with self, returns the exponent of
+    /// a power of two that rounds the same way.
+    ///
+    /// This is the case when self is a multiple of some 2^q, with q two bits
+    /// below the precision, and other is below 2^q: no rounding boundary lies
+    /// between self and the sum.
+    fn negligible(&self, other_top: Option<isize>, precision: usize) -> Option<isize> {
+        let q = self.top_bit()? - precision as isize - 2;
+        match other_top? < q && self.low_bit()? >= q {
+            true => Some(q - 1),
+            false => None,
+        }
+    }
+
+    /// Adds other with the given sign, and rounds the result to `precision`
+    /// bits
+    pub(crate) fn add_round(
+        &mut self,
+        other_sign: bool,
+        other: &BigFloat<T>,
+        precision: usize,
+        mode: RoundingMode,
+    ) {
+        assert!(
+            precision > 0,
+            "A BigFloat needs at least one bit of precision"
+        );
+        if let Some(e) = self.negligible(other.top_bit(), precision) {
+            let tiny = BigFloat::new(T::ONE).mul_pow2(e);
+            self.add_assign(other_sign, tiny.scale, &tiny.int.uint.val);
+        } else if let Some(e) = other.negligible(self.top_bit(), precision) {
+            let sign = self.int.sign;
+            let tiny = BigFloat::new(T::ONE).mul_pow2(e);
+            self.copy_from(other);
+            self.int.sign = other_sign;
+            self.add_assign(sign, tiny.scale, &tiny.int.uint.val);
+        } else {
+            self.add_assign(other_sign, other.scale, &other.int.uint.val);
+        }
+        self.round_bits(precision, mode, false);
+        self.precision = Some(precision);
+    }
+
+    /// Returns self + other, rounded to nearest, ties to even, to `precision`
+    /// bits. The cost does not depend on how far apart the operands are.
+    ///
+    /// ```
+    /// use twibint::BigFloat;
+    ///
+    /// let a = BigFloat::<u64>::from(1u32);
+    /// let b = BigFloat::<u64>::from(3u32) >> 1000;
+    /// assert_eq!(a.add(&b, 10), a);
+    /// assert_eq!(a.add(&(b << 990), 10), BigFloat::from(257u32) >> 8);
+    /// ```
+    pub fn add(&self, other: &BigFloat<T>, precision: usize) -> BigFloat<T> {
+        let mut ret = self.clone();
+        ret.add_round(other.int.sign, other, precision, RoundingMode::NearestEven);
+        ret
+    }
+
+    /// Returns self - other, rounded to nearest, ties to even, to `precision`
+    /// bits
+    pub fn sub(&self, other: &BigFloat<T>, precision: usize) -> BigFloat<T> {
+        let mut ret = self.clone();
+        ret.add_round(!other.int.sign, other, precision, RoundingMode::NearestEven);
+        ret
+    }
 }
 
 impl<T: Digit> Add<T> for BigFloat<T> {
@@ -233,7 +313,15 @@ impl<T: Digit> Add<&BigFloat<T>> for BigFloat<T> {
 
 impl<T: Digit> AddAssign<T> for BigFloat<T> {
     fn add_assign(&mut self, other: T) {
-        self.add_assign(true, 0, &[other])
+        match self.precision {
+            Some(precision) => self.add_round(
+                true,
+                &BigFloat::new(other),
+                precision,
+                RoundingMode::NearestEven,
+            ),
+            None => self.add_assign(true, 0, &[other]),
+        }
     }
 }
 impl<T: Digit> AddAssign<&T> for BigFloat<T> {
@@ -249,7 +337,12 @@ impl<T: Digit> AddAssign<BigFloat<T>> for BigFloat<T> {
 }
 impl<T: Digit> AddAssign<&BigFloat<T>> for BigFloat<T> {
     fn add_assign(&mut self, other: &BigFloat<T>) {
-        self.add_assign(other.int.sign, other.scale, &other.int.uint.val);
+        match self.precision.max(other.precision) {
+            Some(precision) => {
+                self.add_round(other.int.sign, other, precision, RoundingMode::NearestEven)
+            }
+            None => self.add_assign(other.int.sign, other.scale, &other.int.uint.val),
+        }
     }
 }
 
@@ -260,12 +353,25 @@ impl<T: Digit> SubAssign<&T> for BigFloat<T> {
 }
 impl<T: Digit> SubAssign<T> for BigFloat<T> {
     fn sub_assign(&mut self, other: T) {
-        self.sub_assign(true, 0, &[other]);
+        match self.precision {
+            Some(precision) => self.add_round(
+                false,
+                &BigFloat::new(other),
+                precision,
+                RoundingMode::NearestEven,
+            ),
+            None => self.sub_assign(true, 0, &[other]),
+        }
     }
 }
 impl<T: Digit> SubAssign<&BigFloat<T>> for BigFloat<T> {
     fn sub_assign(&mut self, other: &BigFloat<T>) {
-        self.sub_assign(other.int.sign, other.scale, &other.int.uint.val);
+        match self.precision.max(other.precision) {
+            Some(precision) => {
+                self.add_round(!other.int.sign, other, precision, RoundingMode::NearestEven)
+            }
+            None => self.sub_assign(other.int.sign, other.scale, &other.int.uint.val),
+        }
     }
 }
 impl<T: Digit> SubAssign<BigFloat<T>> for BigFloat<T> {
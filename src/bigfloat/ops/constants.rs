@@ -0,0 +1,149 @@
+use super::fixed::{binary_splitting, round_approximation};
+use crate::traits::Digit;
+use crate::BigFloat;
+use crate::BigInt;
+use crate::BigUint;
+use std::sync::Mutex;
+
+/// The most precise approximation of a constant computed so far, as the
+/// number of bits and the little-endian bytes of a fixed point number
+/// within 2 units
+struct Cache(Mutex<(usize, Vec<u8>)>);
+
+static PI: Cache = Cache::new();
+static E: Cache = Cache::new();
+static LN2: Cache = Cache::new();
+
+impl Cache {
+    const fn new() -> Self {
+        Cache(Mutex::new((0, Vec::new())))
+    }
+
+    /// The constant with `w` bits, within 2 units: the cached value without
+    /// its lowest bits if it has enough of them, or a new one from `compute`
+    fn get<T: Digit>(&self, w: usize, compute: fn(usize) -> BigUint<T>) -> BigInt<T> {
+        let cached = {
+            let cache = self.0.lock().unwrap();
+            (cache.0 >= w).then(|| (cache.0, BigUint::<T>::from_bytes_le(&cache.1)))
+        };
+        if let Some((cached_w, value)) = cached {
+            return (value >> (cached_w - w)).into();
+        }
+
+        let value = compute(w);
+        let mut cache = self.0.lock().unwrap();
+        if cache.0 < w {
+            *cache = (w, value.to_bytes_le());
+        }
+        value.into()
+    }
+}
+
+/// t / q with `w` bits, for positive t and q
+fn quotient<T: Digit>(t: BigInt<T>, q: BigInt<T>, w: usize) -> BigUint<T> {
+    (t.uint << w) / q.uint
+}
+
+/// pi with `w` bits, within 2 units, by the Chudnovsky series:
+/// pi = 426880 sqrt(10005) / sum over n >= 0 of
+/// (13591409 + 545140134 n) prod_{j <= n} -(6j - 5)(2j - 1)(6j - 1) / (j^3 640320^3 / 24)
+fn pi_series<T: Digit>(w: usize) -> BigUint<T> {
+    // Each term is more than 2^47 times smaller than the previous one
+    let nb_terms = (w + 64) / 47 + 2;
+    let (_, q, t) = binary_splitting(1, nb_terms, &|n| {
+        let n = n as i128;
+        (
+            BigInt::from(-(6 * n - 5) * (2 * n - 1) * (6 * n - 1)),
+            BigInt::from(n * n * n) * BigInt::from(10939058860032000u64),
+            BigInt::from(13591409 + 545140134 * n),
+        )
+    });
+    let sum = &q * BigInt::from(13591409u32) + t;
+
+    // 8 more bits on the square root make its error negligible
+    let root = (BigUint::<T>::from(10005u32) << (2 * w + 16)).isqrt();
+    (q * BigInt::from(root) * BigInt::from(426880u32)).uint / (sum << 8).uint
+}
+
+/// e with `w` bits, within 2 units, as the sum of the 1 / n!
+fn e_series<T: Digit>(w: usize) -> BigUint<T> {
+    // The terms from n on add up to less than 2 / n!
+    let (mut nb_terms, mut bits) = (1usize, 0f64);
+    while bits < (w + 4) as f64 {
+        nb_terms += 1;
+        bits += (nb_terms as f64).log2();
+    }
+    let one = BigInt::<T>::from(1u32);
+    let (_, q, t) = binary_splitting(1, nb_terms, &|n| {
+        (one.clone(), BigInt::from(n), one.clone())
+    });
+    quotient(&q + t, q, w)
+}
+
+/// ln(2) with `w` bits, within 2 units
+fn ln2_series<T: Digit>(w: usize) -> BigUint<T> {
+    // ln(2) = 2 atanh(1/3) = 2/3 * (1 + sum over n >= 1 of
+    // prod_{j <= n} (2j - 1) / (9 (2j + 1))), and the terms from n on add
+    // up to less than 9^-n
+    let nb_terms = (w + 2) * 1000 / 3169 + 2;
+    let one = BigInt::<T>::from(1u32);
+    let (_, q, t) = binary_splitting(1, nb_terms, &|n| {
+        (
+            BigInt::from(2 * n - 1),
+            BigInt::from(18 * n + 9),
+            one.clone(),
+        )
+    });
+    quotient((&q + t) << 1, q * BigInt::from(3u32), w)
+}
+
+/// pi with `w` bits, within 2 units
+pub(crate) fn pi_fixed<T: Digit>(w: usize) -> BigInt<T> {
+    PI.get(w, pi_series)
+}
+
+/// ln(2) with `w` bits, within 2 units
+pub(crate) fn ln2_fixed<T: Digit>(w: usize) -> BigInt<T> {
+    LN2.get(w, ln2_series)
+}
+
+impl<T: Digit> BigFloat<T> {
+    /// Returns pi, rounded to nearest, ties to even, to `precision` bits.
+    ///
+    /// The constants are cached: the most precise value computed so far is
+    /// reused for any lower precision.
+    ///
+    /// ```
+    /// use twibint::BigFloat;
+    ///
+    /// assert_eq!(f64::from(&BigFloat::<u64>::pi(53)), std::f64::consts::PI);
+    /// assert_eq!(BigFloat::<u64>::pi(3), BigFloat::from(3u32));
+    /// ```
+    pub fn pi(precision: usize) -> BigFloat<T> {
+        round_approximation(precision, |w| (pi_fixed(w), 2, -(w as isize)))
+    }
+
+    /// Returns e, the base of the natural logarithm, rounded to nearest,
+    /// ties to even, to `precision` bits. It is cached like `pi`.
+    ///
+    /// ```
+    /// use twibint::BigFloat;
+    ///
+    /// assert_eq!(f64::from(&BigFloat::<u64>::e(53)), std::f64::consts::E);
+    /// ```
+    pub fn e(precision: usize) -> BigFloat<T> {
+        round_approximation(precision, |w| (E.get(w, e_series), 2, -(w as isize)))
+    }
+
+    /// Returns ln(2), rounded to nearest, ties to even, to `precision` bits.
+    /// It is cached like `pi`.
+    ///
+    /// ```
+    /// use twibint::BigFloat;
+    ///
+    /// assert_eq!(f64::from(&BigFloat::<u64>::ln2(53)), std::f64::consts::LN_2);
+    /// ```
+    pub fn ln2(precision: usize) -> BigFloat<T> {
+        round_approximation(precision, |w| (ln2_fixed(w), 2, -(w as isize)))
+    }
+}
@@ -0,0 +1,148 @@
+use crate::errors::DivisionError;
+use crate::rounding::RoundingMode;
+use crate::traits::{Digit, DivisionResult, RemDiv};
+use crate::BigFloat;
+use std::ops::Div;
+use std::ops::DivAssign;
+
+impl<T: Digit> BigFloat<T> {
+    /// Quotient of self by other, rounded to `precision` bits
+    pub(crate) fn div_round(
+        &self,
+        other: &BigFloat<T>,
+        precision: usize,
+        mode: RoundingMode,
+    ) -> DivisionResult<BigFloat<T>> {
+        assert!(
+            precision > 0,
+            "A BigFloat needs at least one bit of precision"
+        );
+        if other.int.uint.is_zero() {
+            return Err(DivisionError::DivisionByZero);
+        }
+        if self.int.uint.is_zero() {
+            return Ok(BigFloat::default().with_precision(precision));
+        }
+
+        // Scale the mantissas so that their quotient has two more bits than
+        // the precision, the remainder then only decides on the rounding of ties
+        let (n, d) = (&self.int.uint, &other.int.uint);
+        let shift = (precision + 2 + d.nb_bits()) as isize - n.nb_bits() as isize;
+        let (q, r) = match shift {
+            s if s >= 0 => (n << s as usize).rem_div(d)?,
+            s => n.rem_div(&(d << (-s) as usize))?,
+        };
+
+        let exponent = (self.scale - other.scale) * T::NB_BITS as isize - shift;
+        let mut ret = BigFloat::from(q).mul_pow2(exponent);
+        ret.int.sign = self.int.sign == other.int.sign;
+        ret.round_bits(precision, mode, !r.is_zero());
+        ret.precision = Some(precision);
+        Ok(ret)
+    }
+
+    /// Returns self / other, rounded to nearest, ties to even, to `precision`
+    /// bits. Fails on a division by zero.
+    ///
+    /// ```
+    /// use twibint::BigFloat;
+    ///
+    /// let a = BigFloat::<u64>::from(1u32);
+    /// let b = BigFloat::<u64>::from(10u32);
+    /// assert_eq!(a.div(&b, 53).unwrap(), BigFloat::try_from(0.1f64).unwrap());
+    /// assert!(a.div(&BigFloat::default(), 53).is_err());
+    /// ```
+    pub fn div(&self, other: &BigFloat<T>, precision: usize) -> DivisionResult<BigFloat<T>> {
+        self.div_round(other, precision, RoundingMode::NearestEven)
+    }
+
+    /// Returns 1 / self, rounded to nearest, ties to even, to `precision`
+    /// bits. Fails if self is zero.
+    ///
+    /// ```
+    /// use twibint::BigFloat;
+    ///
+    /// let third = BigFloat::<u64>::from(3u32).recip(4).unwrap();
+    /// assert_eq!(third, BigFloat::from(11u32) >> 5);
+    /// ```
+    pub fn recip(&self, precision: usize) -> DivisionResult<BigFloat<T>> {
+        BigFloat::new(T::ONE).div_round(self, precision, RoundingMode::NearestEven)
+    }
+}
+
+/// The quotient is rounded at the largest precision of the operands, at
+/// least one of them must have one
+impl<T: Digit> Div<&BigFloat<T>> for &BigFloat<T> {
+    type Output = BigFloat<T>;
+    fn div(self, other: &BigFloat<T>) -> BigFloat<T> {
+        let precision = self
+            .precision
+            .max(other.precision)
+            .expect("Dividing exact BigFloats needs a precision");
+        self.div_round(other, precision, RoundingMode::NearestEven)
+            .unwrap()
+    }
+}
+impl<T: Digit> Div<BigFloat<T>> for BigFloat<T> {
+    type Output = BigFloat<T>;
+    fn div(self, other: BigFloat<T>) -> BigFloat<T> {
+        &self / &other
+    }
+}
+impl<T: Digit> Div<BigFloat<T>> for &BigFloat<T> {
+    type Output = BigFloat<T>;
+    fn div(self, other: BigFloat<T>) -> BigFloat<T> {
+        self / &other
+    }
+}
+impl<T: Digit> Div<&BigFloat<T>> for BigFloat<T> {
+    type Output = BigFloat<T>;
+    fn div(self, other: &BigFloat<T>) -> BigFloat<T> {
+        &self / other
+    }
+}
+impl<T: Digit> Div<T> for &BigFloat<T> {
+    type Output = BigFloat<T>;
+    fn div(self, other: T) -> BigFloat<T> {
+        self / &BigFloat::new(other)
+    }
+}
+impl<T: Digit> Div<&T> for &BigFloat<T> {
+    type Output = BigFloat<T>;
+    fn div(self, other: &T) -> BigFloat<T> {
+        self / *other
+    }
+}
+impl<T: Digit> Div<T> for BigFloat<T> {
+    type Output = BigFloat<T>;
+    fn div(self, other: T) -> BigFloat<T> {
+        &self / other
+    }
+}
+impl<T: Digit> Div<&T> for BigFloat<T> {
+    type Output = BigFloat<T>;
+    fn div(self, other: &T) -> BigFloat<T> {
+        &self / *other
+    }
+}
+
+impl<T: Digit> DivAssign<&BigFloat<T>> for BigFloat<T> {
+    fn div_assign(&mut self, other: &BigFloat<T>) {
+        *self = &*self / other;
+    }
+}
+impl<T: Digit> DivAssign<BigFloat<T>> for BigFloat<T> {
+    fn div_assign(&mut self, other: BigFloat<T>) {
+        *self = &*self / &other;
+    }
+}
+impl<T: Digit> DivAssign<T> for BigFloat<T> {
+    fn div_assign(&mut self, other: T) {
+        *self = &*self / other;
+    }
+}
+impl<T: Digit> DivAssign<&T> for BigFloat<T> {
+    fn div_assign(&mut self, other: &T) {
+        *self = &*self / *other;
+    }
+}
@@ -0,0 +1,85 @@
+use super::constants::ln2_fixed;
+use super::fixed::round_approximation;
+use super::fixed::{exp_reduced, fixed_div, ln_fixed, ln_mantissa_fixed};
+use crate::traits::Digit;
+use crate::BigFloat;
+use crate::BigInt;
+
+impl<T: Digit> BigFloat<T> {
+    /// Returns e^self, rounded to nearest, ties to even, to `precision` bits.
+    ///
+    /// This will panic if the exponent of the result does not fit in an
+    /// `isize`.
+    ///
+    /// ```
+    /// use twibint::BigFloat;
+    ///
+    /// let e = BigFloat::<u64>::from(1u32).exp(53);
+    /// assert_eq!(f64::from(&e), std::f64::consts::E);
+    /// ```
+    pub fn exp(&self, precision: usize) -> BigFloat<T> {
+        if self.int.uint.is_zero() {
+            return BigFloat::new(T::ONE).with_precision(precision);
+        }
+        round_approximation(precision, |w| {
+            let (k, y, err) = exp_reduced(&self.to_fixed(w), 1, w);
+            (y, err, k - w as isize)
+        })
+    }
+
+    /// Returns the natural logarithm of self, rounded to nearest, ties to
+    /// even, to `precision` bits.
+    ///
+    /// This will panic if `self` is not strictly positive.
+    ///
+    /// ```
+    /// use twibint::BigFloat;
+    ///
+    /// let ln10 = BigFloat::<u64>::from(10u32).ln(53);
+    /// assert_eq!(f64::from(&ln10), std::f64::consts::LN_10);
+    /// ```
+    pub fn ln(&self, precision: usize) -> BigFloat<T> {
+        assert!(
+            self.int.is_sign_positive() && !self.int.uint.is_zero(),
+            "The logarithm is only defined for positive numbers"
+        );
+        if *self == BigFloat::new(T::ONE) {
+            return BigFloat::default().with_precision(precision);
+        }
+        round_approximation(precision, |w| {
+            let (y, err) = ln_fixed(self, w);
+            (y, err, -(w as isize))
+        })
+    }
+
+    /// Returns the base 2 logarithm of self, rounded to nearest, ties to
+    /// even, to `precision` bits.
+    ///
+    /// This will panic if `self` is not strictly positive.
+    ///
+    /// ```
+    /// use twibint::BigFloat;
+    ///
+    /// assert_eq!(BigFloat::<u64>::from(1024u32).log2(10), BigFloat::from(10u32));
+    /// let log2_10 = BigFloat::<u64>::from(10u32).log2(53);
+    /// assert_eq!(f64::from(&log2_10), std::f64::consts::LOG2_10);
+    /// ```
+    pub fn log2(&self, precision: usize) -> BigFloat<T> {
+        assert!(
+            self.int.is_sign_positive() && !self.int.uint.is_zero(),
+            "The logarithm is only defined for positive numbers"
+        );
+        if self.top_bit() == self.low_bit() {
+            return BigFloat::from(self.top_bit().unwrap()).with_precision(precision);
+        }
+
+        // log2(x) = e + ln(m) / ln(2), where |ln(m)| < ln(3/2). An error of
+        // d units on ln(m) and of 2 units on ln(2) gives at most 1.5 d + 2
+        // units on the quotient, plus one for its truncation.
+        round_approximation(precision, |w| {
+            let (e, ln_m, err) = ln_mantissa_fixed(self, w);
+            let y = (BigInt::from(e) << w) + fixed_div(&ln_m, &ln2_fixed(w), w);
+            (y, err + err.div_ceil(2) + 3, -(w as isize))
+        })
+    }
+}
@@ -0,0 +1,360 @@
+//! Fixed point kernels of the elementary functions.
+//!
+//! A fixed point number with `w` bits is a `BigInt` x standing for x / 2^w,
+//! and the errors are counted in units of 2^-w. Every kernel returns a
+//! bound on its error, so that `round_approximation` can tell when an
+//! approximation is good enough to be rounded correctly.
+
+use super::constants::ln2_fixed;
+use crate::traits::Digit;
+use crate::BigFloat;
+use crate::BigInt;
+use crate::BigUint;
+
+impl<T: Digit> BigFloat<T> {
+    /// The fixed point number with `w` bits closest to self toward zero
+    pub(crate) fn to_fixed(&self, w: usize) -> BigInt<T> {
+        let shift = self.scale * T::NB_BITS as isize + w as isize;
+        let uint = match shift >= 0 {
+            true => &self.int.uint << shift as usize,
+            false => &self.int.uint >> (-shift) as usize,
+        };
+        BigInt::<T> {
+            sign: self.int.sign || uint.is_zero(),
+            uint,
+        }
+    }
+}
+
+/// Number of approximations, each about one and a half times as precise as
+/// the previous one, after which `round_approximation` stops refining
+pub(crate) const MAX_ITERATIONS: usize = 16;
+
+/// Rounds to nearest, ties to even, to `precision` bits a value for which
+/// `approximation(w)` gives `(y, err, exponent)`: the value is within `err`
+/// units of `y * 2^exponent`, and the approximation gets better as `w`
+/// grows. `w` grows until the whole error interval rounds to the same
+/// number. That never happens if the value is exactly a tie, so after
+/// `MAX_ITERATIONS` approximations the value is taken to be the tie it
+/// cannot be told apart from.
+pub(crate) fn round_approximation<T: Digit, F>(
+    precision: usize,
+    mut approximation: F,
+) -> BigFloat<T>
+where
+    F: FnMut(usize) -> (BigInt<T>, usize, isize),
+{
+    assert!(
+        precision > 0,
+        "A BigFloat needs at least one bit of precision"
+    );
+    let guard = 32;
+    let mut w = precision + guard;
+    for _ in 0..MAX_ITERATIONS {
+        let (y, err, exponent) = approximation(w);
+        let err = BigInt::<T>::from(err);
+        let mut lower = BigFloat::from(&y - &err).mul_pow2(exponent);
+        let mut upper = BigFloat::from(&y + &err).mul_pow2(exponent);
+        lower.set_precision(precision);
+        upper.set_precision(precision);
+        if lower == upper {
+            return lower;
+        }
+
+        // A small result has fewer significant bits than `w`
+        let significant = y.uint.nb_bits().saturating_sub(err.uint.nb_bits());
+        w += (w / 2).max((precision + guard).saturating_sub(significant));
+    }
+
+    // The error interval still holds a tie, and is much narrower than the
+    // distance between two ties: rounding with one more bit gives that tie
+    let (y, _, exponent) = approximation(w);
+    let mut ret = BigFloat::from(y).mul_pow2(exponent);
+    ret.set_precision(precision + 1);
+    ret.set_precision(precision);
+    ret
+}
+
+/// Binary splitting of the sum over n in [a, b) of
+/// `c(n) * p(a) * ... * p(n) / (q(a) * ... * q(n))`, where `term(n)` is
+/// `(p(n), q(n), c(n))`. Returns `(P, Q, T)`: the products of the p(n), of
+/// the q(n), and the sum times Q.
+pub(crate) fn binary_splitting<T: Digit, F>(
+    a: usize,
+    b: usize,
+    term: &F,
+) -> (BigInt<T>, BigInt<T>, BigInt<T>)
+where
+    F: Fn(usize) -> (BigInt<T>, BigInt<T>, BigInt<T>),
+{
+    debug_assert!(a < b);
+    if b - a == 1 {
+        let (p, q, c) = term(a);
+        let t = &c * &p;
+        return (p, q, t);
+    }
+
+    let m = a + (b - a) / 2;
+    let (p1, q1, t1) = binary_splitting(a, m, term);
+    let (p2, q2, t2) = binary_splitting(m, b, term);
+    let t = &t1 * &q2 + &p1 * &t2;
+    (p1 * p2, q1 * q2, t)
+}
+
+/// t / q with `w` bits, rounded toward zero, within one unit
+pub(crate) fn fixed_div<T: Digit>(t: &BigInt<T>, q: &BigInt<T>, w: usize) -> BigInt<T> {
+    let uint = (&t.uint << w) / &q.uint;
+    BigInt::<T> {
+        sign: t.sign == q.sign || uint.is_zero(),
+        uint,
+    }
+}
+
+/// exp(u / 2^k) with `w` bits, within 2 units, knowing that
+/// |u / 2^k| < 2^-s with s >= 1
+fn exp_series<T: Digit>(u: &BigInt<T>, k: usize, s: usize, w: usize) -> BigInt<T> {
+    // The terms from n on add up to less than 2 * 2^(-s n) / n!, stop at
+    // the first n where this is below a quarter of a unit
+    let (mut n, mut bits) = (0usize, 0f64);
+    while bits < (w + 4) as f64 {
+        n += 1;
+        bits += s as f64 + (n as f64).log2();
+    }
+
+    let one = BigInt::<T>::from(1u32);
+    if n == 1 {
+        return one << w;
+    }
+    let (_, q, t) = binary_splitting(1, n, &|n| (u.clone(), BigInt::from(n) << k, one.clone()));
+    (one << w) + fixed_div(&t, &q, w)
+}
+
+/// Bit-burst splitting of r with `w` bits, for |r / 2^w| < 1: its nonzero
+/// chunks of bits of doubling sizes, as `(u, k, s)` with a chunk standing for
+/// u / 2^k < 2^-s. After the first one, a chunk is below 2^-8.
+fn bit_burst<T: Digit>(r: &BigInt<T>, w: usize) -> Vec<(BigInt<T>, usize, usize)> {
+    let mut chunks = Vec::new();
+    let (mut start, mut end) = (0, w.min(8));
+    while start < w {
+        let high = &r.uint >> (w - end);
+        let uint = &high - ((&r.uint >> (w - start)) << (end - start));
+        if !uint.is_zero() {
+            chunks.push((BigInt::<T> { uint, sign: r.sign }, end, start));
+        }
+        (start, end) = (end, w.min(2 * end));
+    }
+    chunks
+}
+
+/// Increasing numbers of bits for Newton iterations that double the number
+/// of correct bits, from one the f64 functions can start from up to `w`
+fn newton_widths(w: usize) -> Vec<usize> {
+    let mut widths = vec![w];
+    while let Some(&width) = widths.last().filter(|width| **width > 48) {
+        widths.push(width / 2 + 8);
+    }
+    widths.reverse();
+    widths
+}
+
+/// A bound in units of d^2, for d within `d_err` units of `d`
+fn square_bound<T: Digit>(d: &BigInt<T>, d_err: usize, w: usize) -> usize {
+    let d_max = &d.uint + BigUint::<T>::from(d_err);
+    usize::try_from(&((&d_max * &d_max) >> w)).map_or(usize::MAX / 4, |square| square + 1)
+}
+
+/// exp(r / 2^w) with `w` bits for |r / 2^w| < 1/2, and its error
+pub(crate) fn exp_fixed<T: Digit>(r: &BigInt<T>, w: usize) -> (BigInt<T>, usize) {
+    debug_assert!(r.uint.nb_bits() < w);
+
+    // The exponential of each chunk is a series in a rational with a small
+    // numerator. Multiplying by the exponential of a chunk below 2^-8
+    // multiplies the error by less than 1 + 1/128.
+    let mut ret = BigInt::<T>::from(1u32) << w;
+    let mut err: usize = 0;
+    for (i, (u, k, s)) in bit_burst(r, w).into_iter().enumerate() {
+        let factor = exp_series(&u, k, s.max(1), w);
+        match i {
+            0 => (ret, err) = (factor, 2),
+            _ => {
+                ret = (ret * factor) >> w;
+                err += err.div_ceil(128) + 5;
+            }
+        }
+    }
+    (ret, err)
+}
+
+/// exp(z / 2^w) = 2^k * y / 2^w, given z within `z_err` units: returns
+/// `(k, y, err)`, with y/2^w in [1/2, 3/2] and within `err` units
+pub(crate) fn exp_reduced<T: Digit>(
+    z: &BigInt<T>,
+    z_err: usize,
+    w: usize,
+) -> (isize, BigInt<T>, usize) {
+    // z = k ln(2) + r with |r| <= ln(2) / 2, with enough bits of ln(2) for
+    // k ln(2) to be within a quarter of a unit
+    let k_bits = z.uint.nb_bits().saturating_sub(w) + 1;
+    let w2 = w + k_bits + 3;
+    let ln2 = ln2_fixed::<T>(w2);
+    let k = ((&z.uint << (w2 - w)) + (&ln2.uint >> 1)) / &ln2.uint;
+    let k = BigInt::<T> {
+        sign: z.sign || k.is_zero(),
+        uint: k,
+    };
+    let r = z - ((&k * &ln2) >> (w2 - w));
+    let k = isize::try_from(&k).expect("The exponent of the result overflows");
+
+    // An error of d units on r is an error of at most 1.5 d units on exp(r)
+    let (y, err) = exp_fixed(&r, w);
+    (k, y, err + (3 * (z_err + 2)).div_ceil(2) + 1)
+}
+
+/// ln(x) = e ln(2) + ln(m) with m in [3/4, 3/2): returns e, ln(m) with `w`
+/// bits, and its error. x must be strictly positive.
+pub(crate) fn ln_mantissa_fixed<T: Digit>(x: &BigFloat<T>, w: usize) -> (isize, BigInt<T>, usize) {
+    let nb_bits = x.int.uint.nb_bits();
+    let mut e = x.top_bit().unwrap() + 1;
+    if nb_bits < 2 || !x.int.uint.bit(nb_bits - 2) {
+        e -= 1;
+    }
+    let m = x.clone().mul_pow2(-e);
+
+    // Newton iterations y <- y + m exp(-y) - 1 from the f64 logarithm
+    let widths = newton_widths(w);
+    let mut previous = widths[0];
+    let mut y = BigInt::<T>::from((f64::from(&m).ln() * (1u64 << previous) as f64) as i64);
+    for width in widths {
+        y <<= width - previous;
+        previous = width;
+        let (exp, exp_err) = exp_fixed(&-&y, width);
+        let d = ((m.to_fixed(width) * exp) >> width) - (BigInt::<T>::from(1u32) << width);
+        y += &d;
+        if width < w {
+            continue;
+        }
+
+        // With d = m exp(-y) - 1 at the previous y, ln(m) = y + ln(1 + d),
+        // and |ln(1 + d) - d| <= d^2. The truncation of m adds 2 units.
+        let d_err = exp_err + exp_err.div_ceil(2) + 1;
+        return (e, y, square_bound(&d, d_err, w) + d_err + 2);
+    }
+    unreachable!()
+}
+
+/// ln(x) with `w` bits, and its error. x must be strictly positive.
+pub(crate) fn ln_fixed<T: Digit>(x: &BigFloat<T>, w: usize) -> (BigInt<T>, usize) {
+    let (e, mut y, err) = ln_mantissa_fixed(x, w);
+    if e == 0 {
+        return (y, err);
+    }
+
+    // Enough bits of ln(2) for e ln(2) to be within half a unit
+    let w2 = w + (usize::BITS - e.unsigned_abs().leading_zeros()) as usize + 2;
+    y += (ln2_fixed::<T>(w2) * BigInt::from(e)) >> (w2 - w);
+    (y, err + 2)
+}
+
+/// num / den with `w` bits, for num within `num_err` units and den within
+/// `den_err` units, and its error
+pub(crate) fn fixed_quotient<T: Digit>(
+    num: &BigInt<T>,
+    num_err: usize,
+    den: &BigInt<T>,
+    den_err: usize,
+    w: usize,
+) -> (BigInt<T>, usize) {
+    let (a, b) = (BigUint::<T>::from(num_err), BigUint::<T>::from(den_err));
+    if den.uint <= b {
+        return (BigInt::default(), usize::MAX / 4);
+    }
+
+    // |N / D - n / d| <= (|N| b + |D| a) / (|D| (|D| - b)), plus one unit
+    // for the truncation of the quotient
+    let bound = ((&num.uint * &b + &den.uint * &a) << w) / (&den.uint * (&den.uint - &b));
+    let err = usize::try_from(&bound).map_or(usize::MAX / 4, |err| err + 2);
+    (fixed_div(num, den, w), err)
+}
+
+/// cos(u / 2^k) and sin(u / 2^k) with `w` bits, within 2 units each,
+/// knowing that |u / 2^k| < 2^-s
+fn sin_cos_series<T: Digit>(u: &BigInt<T>, k: usize, s: usize, w: usize) -> (BigInt<T>, BigInt<T>) {
+    // Both series alternate with decreasing terms, and the error of a
+    // partial sum is below the first term left out, of degree m below
+    // 2^(-s m) / m!. Stop at the first m where this is below an eighth of
+    // a unit.
+    let (mut m, mut bits) = (0usize, 0f64);
+    while bits < (w + 3) as f64 {
+        m += 1;
+        bits += s as f64 + (m as f64).log2();
+    }
+
+    // The terms of degree below 2n for the cosine, and 2n + 1 for the sine
+    let n = m.div_ceil(2);
+    let one = BigInt::<T>::from(1u32);
+    if n == 1 {
+        return (one << w, u << (w - k));
+    }
+    let square = -(u * u);
+    let series = |offset: usize| {
+        binary_splitting(1, n, &|j| {
+            let q = BigInt::from((2 * j + offset - 1) * (2 * j + offset)) << (2 * k);
+            (square.clone(), q, one.clone())
+        })
+    };
+    let (_, q, t) = series(0);
+    let cos = fixed_div(&(&q + &t), &q, w);
+    let (_, q, t) = series(1);
+    let sin = fixed_div(&(u * (&q + &t)), &(q << k), w);
+    (cos, sin)
+}
+
+/// cos(r / 2^w) and sin(r / 2^w) with `w` bits for |r / 2^w| < 1, and the
+/// error of each
+pub(crate) fn sin_cos_fixed<T: Digit>(r: &BigInt<T>, w: usize) -> (BigInt<T>, BigInt<T>, usize) {
+    debug_assert!(r.uint.nb_bits() <= w);
+
+    // The rotation by a chunk below 2^-8 multiplies the error by less than
+    // 1 + 1/128
+    let mut cos = BigInt::<T>::from(1u32) << w;
+    let mut sin = BigInt::<T>::default();
+    let mut err: usize = 0;
+    for (i, (u, k, s)) in bit_burst(r, w).into_iter().enumerate() {
+        let (c, s) = sin_cos_series(&u, k, s, w);
+        match i {
+            0 => (cos, sin, err) = (c, s, 2),
+            _ => {
+                (cos, sin) = ((&cos * &c - &sin * &s) >> w, (&sin * &c + &cos * &s) >> w);
+                err += err.div_ceil(128) + 5;
+            }
+        }
+    }
+    (cos, sin, err)
+}
+
+/// atan(x / 2^w) with `w` bits for |x / 2^w| <= 1, and its error
+pub(crate) fn atan_fixed<T: Digit>(x: &BigInt<T>, w: usize) -> (BigInt<T>, usize) {
+    // Newton iterations y <- y + d from the f64 arctangent, with
+    // d = (x cos(y) - sin(y)) / (cos(y) + x sin(y)) = tan(atan(x) - y)
+    let widths = newton_widths(w);
+    let mut previous = widths[0];
+    let start = f64::from(&BigFloat::from(x.clone()).mul_pow2(-(w as isize))).atan();
+    let mut y = BigInt::<T>::from((start * (1u64 << previous) as f64) as i64);
+    for width in widths {
+        y <<= width - previous;
+        previous = width;
+        let x = x >> (w - width);
+        let (cos, sin, err) = sin_cos_fixed(&y, width);
+        let num = ((&x * &cos) >> width) - &sin;
+        let den = &cos + ((&x * &sin) >> width);
+        let (d, d_err) = fixed_quotient(&num, 2 * err + 1, &den, 2 * err + 1, width);
+        y += &d;
+        if width < w {
+            continue;
+        }
+
+        // atan(x) = y + atan(d) at the previous y, and |atan(d) - d| <= d^2
+        return (y, square_bound(&d, d_err, w).saturating_add(d_err));
+    }
+    unreachable!()
+}
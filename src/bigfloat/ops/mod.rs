@@ -1,7 +1,14 @@
 mod addsub;
+mod constants;
+mod div;
+mod exp;
+mod fixed;
 mod mul;
 mod neg;
+mod pow;
 mod shift;
+mod sqrt;
+mod trig;
 
 #[cfg(test)]
 mod tests;
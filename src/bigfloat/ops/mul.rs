@@ -1,3 +1,4 @@
+use crate::rounding::RoundingMode;
 use crate::BigFloat;
 use std::iter::Product;
 use std::ops::Mul;
@@ -31,11 +32,51 @@ impl<T: Digit> BigFloat<T> {
         self.int._set_to_mul(a_sign, a, b_sign, b);
         self.scale = a_scale + b_scale;
     }
+
+    /// Exact product of self and other
+    fn exact_mul(&self, other: &BigFloat<T>) -> BigFloat<T> {
+        let mut ret = BigFloat::default()
+            .with_capacity((self.int.uint.val.len() + other.int.uint.val.len()) * T::NB_BITS);
+        ret.set_to_mul(self, other);
+        ret.simplify();
+        ret
+    }
+
+    /// Product of self and other, rounded to `precision` bits
+    pub(crate) fn mul_round(
+        &self,
+        other: &BigFloat<T>,
+        precision: usize,
+        mode: RoundingMode,
+    ) -> BigFloat<T> {
+        assert!(
+            precision > 0,
+            "A BigFloat needs at least one bit of precision"
+        );
+        let mut ret = self.exact_mul(other);
+        ret.round_bits(precision, mode, false);
+        ret.precision = Some(precision);
+        ret
+    }
+
+    /// Returns self * other, rounded to nearest, ties to even, to `precision`
+    /// bits
+    ///
+    /// ```
+    /// use twibint::BigFloat;
+    ///
+    /// let a = BigFloat::<u64>::from(7u32);
+    /// assert_eq!(a.mul(&a, 3), BigFloat::from(48u32));
+    /// ```
+    pub fn mul(&self, other: &BigFloat<T>, precision: usize) -> BigFloat<T> {
+        self.mul_round(other, precision, RoundingMode::NearestEven)
+    }
 }
 
 impl<T: Digit> MulAssign<T> for BigFloat<T> {
     fn mul_assign(&mut self, other: T) {
         self.int *= other;
+        self.round_to_precision();
     }
 }
 impl<T: Digit> MulAssign<&T> for BigFloat<T> {
@@ -87,18 +128,10 @@ impl<T: Digit> Mul<&T> for BigFloat<T> {
 impl<T: Digit> Mul<&BigFloat<T>> for &BigFloat<T> {
     type Output = BigFloat<T>;
     fn mul(self, other: &BigFloat<T>) -> BigFloat<T> {
-        let mut ret = BigFloat::default()
-            .with_capacity((self.int.uint.val.len() + other.int.uint.val.len()) * T::NB_BITS);
-        ret._set_to_mul(
-            self.int.sign,
-            self.scale,
-            &self.int.uint.val,
-            other.int.sign,
-            other.scale,
-            &other.int.uint.val,
-        );
-        ret.simplify();
-        ret
+        match self.precision.max(other.precision) {
+            Some(precision) => self.mul_round(other, precision, RoundingMode::NearestEven),
+            None => self.exact_mul(other),
+        }
     }
 }
 impl<T: Digit> Mul<BigFloat<T>> for BigFloat<T> {
@@ -0,0 +1,127 @@
+use super::fixed::{exp_reduced, ln_fixed, round_approximation};
+use crate::rounding::RoundingMode;
+use crate::traits::{Digit, Pow};
+use crate::BigFloat;
+use crate::BigInt;
+use crate::BigUint;
+
+/// The 2^j-th root of an odd integer, if it is a perfect 2^j-th power
+fn root_pow2<T: Digit>(odd: &BigUint<T>, j: usize) -> Option<BigUint<T>> {
+    if *odd == BigUint::new(T::ONE) {
+        return Some(odd.clone());
+    }
+    // A root of at least 3 has a 2^j-th power of more than 2^j bits
+    if j >= usize::BITS as usize - 1 || (1 << j) >= odd.nb_bits() {
+        return None;
+    }
+
+    let mut root = odd.clone();
+    for _ in 0..j {
+        let (s, remainder) = root.sqrt_rem();
+        if !remainder.is_zero() {
+            return None;
+        }
+        root = s;
+    }
+    Some(root)
+}
+
+impl<T: Digit> BigFloat<T> {
+    /// odd * 2^e raised to the integer power n, if the result has at most
+    /// `precision + 1` significant bits, the only case where it can be a tie
+    fn exact_pow(odd: &BigUint<T>, e: isize, n: &BigInt<T>, precision: usize) -> Option<Self> {
+        let one = BigUint::new(T::ONE);
+        if *odd == one {
+            let exponent = isize::try_from(n)
+                .ok()
+                .and_then(|n| n.checked_mul(e))
+                .expect("The exponent of the result overflows");
+            return Some(BigFloat::from(one).mul_pow2(exponent));
+        }
+
+        // The power of an odd number above 1 has at least n (bits - 1) + 1
+        // significant bits, and its inverse is not a dyadic rational
+        let n = usize::try_from(n).ok()?;
+        if n.checked_mul(odd.nb_bits() - 1)? > precision {
+            return None;
+        }
+        let mut ret = BigFloat::from(odd.pow(n)).mul_pow2(e * n as isize);
+        ret.round_bits(precision, RoundingMode::NearestEven, false);
+        Some(ret)
+    }
+
+    /// Returns self raised to the power y, rounded to nearest, ties to even,
+    /// to `precision` bits. Zero to the power zero is one.
+    ///
+    /// This will panic if `self` is zero and `y` negative, if `self` is
+    /// negative and `y` is not an integer, or if the exponent of the result
+    /// does not fit in an `isize`.
+    ///
+    /// ```
+    /// use twibint::BigFloat;
+    ///
+    /// let x = BigFloat::<u64>::from(2u32);
+    /// let y = BigFloat::<u64>::from(1u32) >> 1;
+    /// assert_eq!(f64::from(&x.pow(&y, 53)), std::f64::consts::SQRT_2);
+    ///
+    /// let x = BigFloat::<u64>::from(-3i32);
+    /// assert_eq!(x.pow(&BigFloat::from(3u32), 53), BigFloat::from(-27i32));
+    /// ```
+    pub fn pow(&self, y: &BigFloat<T>, precision: usize) -> BigFloat<T> {
+        assert!(
+            precision > 0,
+            "A BigFloat needs at least one bit of precision"
+        );
+        if y.int.uint.is_zero() {
+            return BigFloat::new(T::ONE).with_precision(precision);
+        }
+        if self.int.uint.is_zero() {
+            assert!(
+                y.int.is_sign_positive(),
+                "Zero has no power with a negative exponent"
+            );
+            return BigFloat::default().with_precision(precision);
+        }
+
+        let y_low = y.low_bit().unwrap();
+        assert!(
+            self.int.is_sign_positive() || y_low >= 0,
+            "A negative number only has powers with an integer exponent"
+        );
+        let mut x = self.clone();
+        x.int.sign = true;
+
+        let mut ret = x.pow_positive(y, precision);
+        ret.int.sign = self.int.is_sign_positive() || y_low > 0;
+        ret.precision = Some(precision);
+        ret
+    }
+
+    /// Power of a strictly positive number
+    fn pow_positive(&self, y: &BigFloat<T>, precision: usize) -> BigFloat<T> {
+        // self = odd * 2^e, and y = n / 2^j with n odd if j > 0. Then self^y
+        // is a dyadic rational, and possibly a tie, only if odd is a perfect
+        // 2^j-th power and 2^j divides e.
+        let e = self.low_bit().unwrap();
+        let odd = &self.int.uint >> self.int.uint.trailing_zeros().unwrap();
+        let j = (-y.low_bit().unwrap()).max(0) as usize;
+        let divides = e == 0 || e.trailing_zeros() as usize >= j;
+        if let Some(root) = root_pow2(&odd, j).filter(|_| divides) {
+            let n = y.to_fixed(j);
+            if let Some(ret) = BigFloat::exact_pow(&root, e >> j, &n, precision) {
+                return ret;
+            }
+        }
+
+        // self^y = exp(y ln(self)), with enough bits of the logarithm for the
+        // error on the product to be a sixteenth of its own error
+        round_approximation(precision, |w| {
+            let w_ln = w + (y.top_bit().unwrap() + 1).max(0) as usize + 4;
+            let (ln, ln_err) = ln_fixed(self, w_ln);
+            let exponent = y.scale * T::NB_BITS as isize - w_ln as isize;
+            let z = BigFloat::from(ln * &y.int).mul_pow2(exponent).to_fixed(w);
+            let (k, y, err) = exp_reduced(&z, ln_err.div_ceil(16) + 1, w);
+            (y, err, k - w as isize)
+        })
+    }
+}
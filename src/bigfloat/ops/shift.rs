@@ -8,12 +8,27 @@ use crate::BigFloat;
 
 impl<T: Digit> BigFloat<T> {
     pub(crate) fn shl(&mut self, b: usize) {
+        if self.int.uint.is_zero() {
+            return;
+        }
         self.int <<= b % T::NB_BITS;
         let scale_offset: isize = (b / T::NB_BITS).try_into().unwrap();
         self.scale += scale_offset;
     }
 
+    /// Multiplies by 2^exponent
+    pub(crate) fn mul_pow2(mut self, exponent: isize) -> Self {
+        match exponent >= 0 {
+            true => self <<= exponent as usize,
+            false => self >>= exponent.unsigned_abs(),
+        }
+        self
+    }
+
     fn shr(&mut self, b: usize) {
+        if self.int.uint.is_zero() {
+            return;
+        }
         let small_shift = b % T::NB_BITS;
         let big_shift: isize = (b / T::NB_BITS).try_into().unwrap();
         self.scale -= big_shift;
@@ -0,0 +1,46 @@
+use crate::rounding::RoundingMode;
+use crate::traits::Digit;
+use crate::BigFloat;
+
+impl<T: Digit> BigFloat<T> {
+    /// Returns the square root of self, rounded to nearest, ties to even, to
+    /// `precision` bits.
+    ///
+    /// This will panic if `self` is negative.
+    ///
+    /// ```
+    /// use twibint::BigFloat;
+    ///
+    /// let n = BigFloat::<u64>::from(2u32);
+    /// assert_eq!(f64::from(&n.sqrt(53)), std::f64::consts::SQRT_2);
+    /// assert_eq!(BigFloat::<u64>::from(9u32).sqrt(1), BigFloat::from(4u32));
+    /// ```
+    pub fn sqrt(&self, precision: usize) -> BigFloat<T> {
+        assert!(
+            precision > 0,
+            "A BigFloat needs at least one bit of precision"
+        );
+        assert!(
+            !self.int.is_sign_negative(),
+            "The square root is only defined for non-negative numbers"
+        );
+        if self.int.uint.is_zero() {
+            return BigFloat::default().with_precision(precision);
+        }
+
+        // Scale the mantissa by an even power of two so that its integer
+        // square root has two more bits than the precision, the remainder
+        // then only decides on the rounding of ties
+        let exponent = self.scale * T::NB_BITS as isize;
+        let mut shift = (2 * precision + 4).saturating_sub(self.int.uint.nb_bits());
+        if (exponent - shift as isize) % 2 != 0 {
+            shift += 1;
+        }
+        let (root, remainder) = (&self.int.uint << shift).sqrt_rem();
+
+        let mut ret = BigFloat::from(root).mul_pow2((exponent - shift as isize) / 2);
+        ret.round_bits(precision, RoundingMode::NearestEven, !remainder.is_zero());
+        ret.precision = Some(precision);
+        ret
+    }
+}
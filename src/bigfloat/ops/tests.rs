@@ -1,3 +1,4 @@
+use crate::test_util::TestRng;
 use crate::traits::Digit;
 use crate::BigFloat;
 use crate::BigInt;
@@ -467,3 +468,338 @@ fn sub4<T: Digit>() {
     let ret = BigFloat::from(ret);
     assert_eq!(ret, c);
 }
+
+/// Random floats with moderate exponents, so that their quotients are normal
+fn random_floats(n: usize) -> Vec<f64> {
+    let mut rng = TestRng::new(1);
+    (0..n)
+        .map(|_| {
+            let state = rng.next_u64();
+            let mantissa = (state >> 11) as f64;
+            let exponent = (state % 200) as i32 - 153;
+            match state & (1 << 10) {
+                0 => mantissa * 2f64.powi(exponent),
+                _ => -mantissa * 2f64.powi(exponent),
+            }
+        })
+        .collect()
+}
+
+/// Division is correctly rounded, like the division of f64 and f32
+#[test_with(u32, u64)]
+fn div_like_floats<T: Digit>() {
+    let floats = random_floats(40);
+    for fa in &floats {
+        for fb in &floats {
+            let a = BigFloat::<T>::try_from(*fa).unwrap();
+            let b = BigFloat::<T>::try_from(*fb).unwrap();
+            let q = a.div(&b, 53).unwrap();
+            assert_eq!(q, BigFloat::try_from(fa / fb).unwrap(), "{fa} / {fb}");
+            assert_eq!(q.precision(), Some(53));
+            assert_eq!(&a / &b, q);
+
+            // Quotients of f32 in the normal range
+            if fa.abs().log2().abs() > 60.0 || fb.abs().log2().abs() > 60.0 {
+                continue;
+            }
+            let (fa, fb) = (*fa as f32, *fb as f32);
+            let a = BigFloat::<T>::try_from(fa).unwrap();
+            let b = BigFloat::<T>::try_from(fb).unwrap();
+            assert_eq!(a.div(&b, 24).unwrap(), BigFloat::try_from(fa / fb).unwrap());
+        }
+    }
+}
+
+#[test_with(u32, u64)]
+fn recip<T: Digit>() {
+    // 1/3 = 0.0101..., rounded up at 200 bits
+    let three = BigFloat::<T>::from(3u32);
+    let expected = (BigUint::<T>::from(1u32) << 201) + BigUint::from(1u32);
+    let expected = BigFloat::from(expected / BigUint::from(3u32)) >> 201;
+    assert_eq!(three.recip(200).unwrap(), expected);
+    assert_eq!((-&three).recip(200).unwrap(), -expected);
+
+    assert_eq!(
+        (BigFloat::<T>::from(1u32) >> 1000).recip(1).unwrap(),
+        BigFloat::from(1u32) << 1000
+    );
+    assert!(BigFloat::<T>::default().recip(10).is_err());
+
+    // Divisors longer than the precision
+    let n = BigFloat::<T>::from("1e500");
+    assert_eq!(
+        n.recip(64).unwrap(),
+        BigFloat::from_str_with_precision("1e-500", 64).unwrap()
+    );
+    assert_eq!(
+        BigFloat::<T>::from(7u32).div(&n, 100).unwrap(),
+        BigFloat::from_str_with_precision("7e-500", 100).unwrap()
+    );
+}
+
+#[test_with(u32, u64)]
+fn div_operators<T: Digit>() {
+    let one = BigFloat::<T>::from(1u32);
+    let a = BigFloat::<T>::from(2u32).with_precision(10);
+
+    let q = &one / &a;
+    assert_eq!(q, &one >> 1);
+    assert_eq!(q.precision(), Some(10));
+    assert_eq!((&a / BigFloat::from(3u32)), BigFloat::from(683u32) >> 10);
+    assert_eq!(
+        (&a / BigFloat::from(3u32).with_precision(20)).precision(),
+        Some(20)
+    );
+
+    let mut b = BigFloat::<T>::from(1u32).with_precision(3);
+    b /= T::decomposition_from_u32(5)[0];
+    assert_eq!(b, BigFloat::from(3u32) >> 4);
+    b /= &a;
+    assert_eq!(b, BigFloat::from(3u32) >> 5);
+    assert_eq!(BigFloat::default() / a, BigFloat::default());
+}
+
+#[test]
+#[should_panic]
+fn div_exact() {
+    let _ = BigFloat::<u64>::from(1u32) / BigFloat::from(3u32);
+}
+
+/// The square root is correctly rounded, like the one of f64
+#[test_with(u32, u64)]
+fn sqrt_like_floats<T: Digit>() {
+    for f in random_floats(200) {
+        let f = f.abs();
+        let root = BigFloat::<T>::try_from(f).unwrap().sqrt(53);
+        assert_eq!(root, BigFloat::try_from(f.sqrt()).unwrap(), "sqrt({f})");
+        assert_eq!(root.precision(), Some(53));
+    }
+    assert_eq!(BigFloat::<T>::default().sqrt(10), BigFloat::default());
+    assert_eq!(
+        BigFloat::<T>::from(1u32 << 20).sqrt(1),
+        BigFloat::from(1u32 << 10)
+    );
+    assert_eq!(
+        BigFloat::<T>::from(2u32).sqrt(4),
+        BigFloat::from(11u32) >> 3
+    );
+}
+
+/// Checks that a function at low precisions is the rounding of its value at
+/// a much higher precision, and close to the f64 one
+fn check_rounding<T: Digit>(x: f64, f: impl Fn(usize) -> BigFloat<T>, expected: f64) {
+    let reference = f(300);
+    assert!((f64::from(&reference) - expected).abs() <= expected.abs() * 1e-14);
+    for precision in (1..=24).chain([53, 100]) {
+        let ret = f(precision);
+        assert_eq!(
+            ret,
+            reference.clone().with_precision(precision),
+            "{x} at {precision} bits"
+        );
+        assert_eq!(ret.precision(), Some(precision));
+    }
+}
+
+#[test_with(u32, u64)]
+fn exp<T: Digit>() {
+    for f in random_floats(30).into_iter().map(|f| f % 40.0) {
+        let x = BigFloat::<T>::try_from(f).unwrap();
+        check_rounding(f, |p| x.exp(p), f.exp());
+    }
+    assert_eq!(BigFloat::<T>::default().exp(5), BigFloat::from(1u32));
+
+    let e = BigFloat::<T>::from_str_with_precision(
+        "2.71828182845904523536028747135266249775724709369995",
+        160,
+    );
+    assert_eq!(BigFloat::<T>::from(1u32).exp(160), e.unwrap());
+
+    // Far from the range of f64
+    let x = BigFloat::<T>::from(-100000i32);
+    let expected = BigFloat::<T>::from_str_with_precision("3.56294956530937e-43430", 40);
+    assert_eq!(x.exp(40), expected.unwrap());
+}
+
+#[test_with(u32, u64)]
+fn ln<T: Digit>() {
+    for f in random_floats(30).into_iter().map(f64::abs) {
+        let x = BigFloat::<T>::try_from(f).unwrap();
+        check_rounding(f, |p| x.ln(p), f.ln());
+        check_rounding(f, |p| x.log2(p), f.log2());
+    }
+
+    // Close to one, the logarithm is small
+    for f in [1.0 + f64::EPSILON, 1.0 - f64::EPSILON / 2.0, 1.5, 0.75] {
+        let x = BigFloat::<T>::try_from(f).unwrap();
+        check_rounding(f, |p| x.ln(p), f.ln());
+        check_rounding(f, |p| x.log2(p), f.log2());
+    }
+
+    let ln2 = BigFloat::<T>::from_str_with_precision(
+        "0.69314718055994530941723212145817656807550013436025",
+        160,
+    );
+    assert_eq!(BigFloat::<T>::from(2u32).ln(160), ln2.unwrap());
+
+    assert_eq!(BigFloat::<T>::from(1u32).ln(5), BigFloat::default());
+    assert_eq!(BigFloat::<T>::from(1u32).log2(5), BigFloat::default());
+    assert_eq!(
+        (BigFloat::<T>::from(1u32) >> 100).log2(7),
+        BigFloat::from(-100i32)
+    );
+    assert_eq!(
+        BigFloat::<T>::from(1u32 << 30).log2(2),
+        BigFloat::from(32u32)
+    );
+}
+
+#[test_with(u32, u64)]
+fn pow<T: Digit>() {
+    let floats = random_floats(12);
+    for fx in floats.iter().map(|f| f.abs()) {
+        for fy in floats.iter().map(|f| f % 8.0) {
+            let (x, y) = (BigFloat::<T>::try_from(fx), BigFloat::<T>::try_from(fy));
+            let (x, y) = (x.unwrap(), y.unwrap());
+            let expected = fx.powf(fy);
+            if expected.is_normal() {
+                check_rounding(fx, |p| x.pow(&y, p), expected);
+            }
+        }
+    }
+
+    let pow = |x: &str, y: &str, precision| {
+        let (x, y) = (BigFloat::<T>::from(x), BigFloat::<T>::from(y));
+        x.pow(&y, precision)
+    };
+    assert_eq!(pow("0", "0", 5), BigFloat::from(1u32));
+    assert_eq!(pow("0", "2.5", 5), BigFloat::default());
+    assert_eq!(pow("-3", "3", 5), BigFloat::from(-27i32));
+    assert_eq!(pow("-3", "3", 4), BigFloat::from(-28i32));
+    assert_eq!(pow("-2", "-2", 5), BigFloat::from("0.25"));
+    assert_eq!(
+        pow("3", "40", 53),
+        BigFloat::from(3u32.pow(20)).pow(&2u32.into(), 53)
+    );
+    assert_eq!(pow("0.5", "-3000", 1), BigFloat::from(1u32) << 3000);
+
+    // Exact results that are ties
+    assert_eq!(pow("2.25", "0.5", 1), BigFloat::from(2u32));
+    assert_eq!(pow("6.25", "1.5", 6), BigFloat::from("15.5"));
+    assert_eq!(pow("6.25", "-0.5", 3), BigFloat::from("0.375"));
+    assert_eq!(pow("1.0625", "2", 4), BigFloat::from("1.125"));
+    assert_eq!(pow("5.0625", "0.25", 1), BigFloat::from(2u32));
+    assert_eq!(pow("5.0625", "0.25", 2), BigFloat::from("1.5"));
+}
+
+#[test]
+#[should_panic]
+fn ln_negative() {
+    let _ = BigFloat::<u64>::from(-1i32).ln(10);
+}
+
+#[test]
+#[should_panic]
+fn pow_negative() {
+    let _ = BigFloat::<u64>::from(-4i32).pow(&BigFloat::from("0.5"), 10);
+}
+
+#[test_with(u32, u64)]
+fn constants<T: Digit>() {
+    let pi = "3.14159265358979323846264338327950288419716939937510";
+    let e = "2.71828182845904523536028747135266249775724709369995";
+    let ln2 = "0.69314718055994530941723212145817656807550013436025";
+    for (value, expected) in [
+        (BigFloat::<T>::pi(160), pi),
+        (BigFloat::<T>::e(160), e),
+        (BigFloat::<T>::ln2(160), ln2),
+    ] {
+        assert_eq!(
+            value,
+            BigFloat::from_str_with_precision(expected, 160).unwrap()
+        );
+        assert_eq!(value.precision(), Some(160));
+    }
+
+    // Lower precisions come from the cached value
+    let pi = BigFloat::<T>::pi(2000);
+    for precision in 1..100 {
+        assert_eq!(
+            BigFloat::pi(precision),
+            pi.clone().with_precision(precision)
+        );
+    }
+    assert_eq!(BigFloat::<T>::pi(2), BigFloat::from(3u32));
+}
+
+#[test_with(u32, u64)]
+fn sin_cos_tan<T: Digit>() {
+    for f in random_floats(30).into_iter().map(|f| f % 1000.0) {
+        let x = BigFloat::<T>::try_from(f).unwrap();
+        check_rounding(f, |p| x.sin(p), f.sin());
+        check_rounding(f, |p| x.cos(p), f.cos());
+        check_rounding(f, |p| x.tan(p), f.tan());
+    }
+
+    let zero = BigFloat::<T>::default();
+    assert_eq!(zero.sin(5), BigFloat::default());
+    assert_eq!(zero.cos(5), BigFloat::from(1u32));
+    assert_eq!(zero.tan(5), BigFloat::default());
+
+    // Close to a multiple of pi, the sine is the difference with it
+    let (pi, more_pi) = (BigFloat::<T>::pi(200), BigFloat::<T>::pi(400));
+    assert_eq!(pi.sin(53), (&more_pi - &pi).with_precision(53));
+    assert_eq!(
+        (&pi >> 1).cos(53),
+        ((&more_pi - &pi) >> 1).with_precision(53)
+    );
+
+    // Far from the range of f64
+    let x = BigFloat::<T>::from(1u32) << 1000;
+    let expected = BigFloat::<T>::from_str_with_precision(
+        "-0.15920170308624243824004863082083903381368689877746",
+        60,
+    );
+    assert_eq!(x.sin(60), expected.unwrap());
+}
+
+#[test_with(u32, u64)]
+fn atan<T: Digit>() {
+    let floats = random_floats(16);
+    for fy in &floats {
+        let y = BigFloat::<T>::try_from(*fy).unwrap();
+        check_rounding(*fy, |p| y.atan(p), fy.atan());
+        for fx in &floats {
+            let x = BigFloat::<T>::try_from(*fx).unwrap();
+            check_rounding(*fy, |p| y.atan2(&x, p), fy.atan2(*fx));
+        }
+    }
+
+    let (zero, one) = (BigFloat::<T>::default(), BigFloat::<T>::from(1u32));
+    let minus_one = BigFloat::<T>::from(-1i32);
+    assert_eq!(zero.atan(5), BigFloat::default());
+    assert_eq!(zero.atan2(&zero, 5), BigFloat::default());
+    assert_eq!(zero.atan2(&minus_one, 60), BigFloat::pi(60));
+    assert_eq!(one.atan2(&zero, 60), BigFloat::pi(60) >> 1);
+    assert_eq!(minus_one.atan2(&zero, 60), -(BigFloat::pi(60) >> 1));
+    assert_eq!(one.atan2(&one, 60), BigFloat::pi(60) >> 2);
+    assert_eq!(minus_one.atan(60), -(BigFloat::pi(60) >> 2));
+}
+
+#[test_with(u32, u64)]
+fn round_approximation_of_a_tie<T: Digit>() {
+    use super::fixed::{round_approximation, MAX_ITERATIONS};
+
+    // 0b10001 and 0b10011 are ties at 4 bits, that no approximation can
+    // round, and that go to the even neighbour
+    for (tie, expected) in [(0b10001u32, 0b10000u32), (0b10011, 0b10100)] {
+        let mut calls = 0;
+        let n = round_approximation::<T, _>(4, |w| {
+            calls += 1;
+            (BigInt::from(tie) << w, 1, -(w as isize))
+        });
+        assert_eq!(n, BigFloat::from(expected));
+        assert_eq!(n.precision(), Some(4));
+        assert_eq!(calls, MAX_ITERATIONS + 1);
+    }
+}
@@ -0,0 +1,166 @@
+use super::constants::pi_fixed;
+use super::fixed::{atan_fixed, fixed_quotient, round_approximation, sin_cos_fixed};
+use crate::traits::Digit;
+use crate::BigFloat;
+use crate::BigInt;
+use std::cmp::Ordering;
+
+impl<T: Digit> BigFloat<T> {
+    /// self = k pi/2 + r with |r| <= pi/4: returns k modulo 4, and r with `w`
+    /// bits, within 3 units
+    fn reduce_half_pi(&self, w: usize) -> (u64, BigInt<T>) {
+        // Enough bits of pi/2 for k pi/2 to be within a quarter of a unit
+        let x = self.to_fixed(w);
+        let k_bits = x.uint.nb_bits().saturating_sub(w) + 1;
+        let w2 = w + k_bits + 3;
+        let half_pi = pi_fixed::<T>(w2) >> 1;
+        let k = ((&x.uint << (w2 - w)) + (&half_pi.uint >> 1)) / &half_pi.uint;
+        let k = BigInt::<T> {
+            sign: x.sign || k.is_zero(),
+            uint: k,
+        };
+        let r = &x - ((&k * &half_pi) >> (w2 - w));
+
+        let k = k.uint.to_u64_wrapping() & 3;
+        match x.sign {
+            true => (k, r),
+            false => ((4 - k) & 3, r),
+        }
+    }
+
+    /// Returns the sine of self, rounded to nearest, ties to even, to
+    /// `precision` bits.
+    ///
+    /// ```
+    /// use twibint::BigFloat;
+    ///
+    /// let x = BigFloat::<u64>::from(1u32);
+    /// assert_eq!(f64::from(&x.sin(53)), 1f64.sin());
+    /// ```
+    pub fn sin(&self, precision: usize) -> BigFloat<T> {
+        if self.int.uint.is_zero() {
+            return BigFloat::default().with_precision(precision);
+        }
+        round_approximation(precision, |w| {
+            let (k, r) = self.reduce_half_pi(w);
+            let (cos, sin, err) = sin_cos_fixed(&r, w);
+            let y = match k {
+                0 => sin,
+                1 => cos,
+                2 => -sin,
+                _ => -cos,
+            };
+            (y, err + 3, -(w as isize))
+        })
+    }
+
+    /// Returns the cosine of self, rounded to nearest, ties to even, to
+    /// `precision` bits.
+    ///
+    /// ```
+    /// use twibint::BigFloat;
+    ///
+    /// let x = BigFloat::<u64>::from(1u32);
+    /// assert_eq!(f64::from(&x.cos(53)), 1f64.cos());
+    /// ```
+    pub fn cos(&self, precision: usize) -> BigFloat<T> {
+        if self.int.uint.is_zero() {
+            return BigFloat::new(T::ONE).with_precision(precision);
+        }
+        round_approximation(precision, |w| {
+            let (k, r) = self.reduce_half_pi(w);
+            let (cos, sin, err) = sin_cos_fixed(&r, w);
+            let y = match k {
+                0 => cos,
+                1 => -sin,
+                2 => -cos,
+                _ => sin,
+            };
+            (y, err + 3, -(w as isize))
+        })
+    }
+
+    /// Returns the tangent of self, rounded to nearest, ties to even, to
+    /// `precision` bits.
+    ///
+    /// ```
+    /// use twibint::BigFloat;
+    ///
+    /// let x = BigFloat::<u64>::from(1u32);
+    /// assert_eq!(f64::from(&x.tan(53)), 1f64.tan());
+    /// ```
+    pub fn tan(&self, precision: usize) -> BigFloat<T> {
+        if self.int.uint.is_zero() {
+            return BigFloat::default().with_precision(precision);
+        }
+        round_approximation(precision, |w| {
+            let (k, r) = self.reduce_half_pi(w);
+            let (cos, sin, err) = sin_cos_fixed(&r, w);
+            let (y, err) = match k % 2 {
+                0 => fixed_quotient(&sin, err + 3, &cos, err + 3, w),
+                _ => fixed_quotient(&-cos, err + 3, &sin, err + 3, w),
+            };
+            (y, err, -(w as isize))
+        })
+    }
+
+    /// Returns the arctangent of self, in [-pi/2, pi/2], rounded to nearest,
+    /// ties to even, to `precision` bits.
+    ///
+    /// ```
+    /// use twibint::BigFloat;
+    ///
+    /// let x = BigFloat::<u64>::from(1u32);
+    /// assert_eq!(x.atan(100), BigFloat::pi(100) >> 2);
+    /// ```
+    pub fn atan(&self, precision: usize) -> BigFloat<T> {
+        self.atan2(&BigFloat::new(T::ONE), precision)
+    }
+
+    /// Returns the angle of the point (x, self) from the positive x axis,
+    /// in ]-pi, pi], rounded to nearest, ties to even, to `precision` bits.
+    /// It is zero for the origin.
+    ///
+    /// ```
+    /// use twibint::BigFloat;
+    ///
+    /// let y = BigFloat::<u64>::from(1u32);
+    /// let x = BigFloat::<u64>::from(-1i32);
+    /// assert_eq!(f64::from(&y.atan2(&x, 53)), 1f64.atan2(-1.0));
+    /// assert_eq!(BigFloat::<u64>::default().atan2(&x, 53), BigFloat::pi(53));
+    /// ```
+    pub fn atan2(&self, x: &BigFloat<T>, precision: usize) -> BigFloat<T> {
+        let y = self;
+        let (y_negative, x_negative) = (y.int.is_sign_negative(), x.int.is_sign_negative());
+        if y.int.uint.is_zero() {
+            return match x_negative {
+                true => BigFloat::pi(precision),
+                false => BigFloat::default().with_precision(precision),
+            };
+        }
+        if x.int.uint.is_zero() {
+            let half_pi = BigFloat::pi(precision) >> 1;
+            return if y_negative { -half_pi } else { half_pi };
+        }
+
+        // atan2(y, x) is atan(y / x), plus or minus pi if x is negative, and
+        // also +/- pi/2 - atan(x / y), so that the ratio is at most one
+        let swap = y.float_unsigned_ord(x.scale, &x.int.uint.val) == Ordering::Greater;
+        round_approximation(precision, |w| {
+            let (num, den) = if swap { (x, y) } else { (y, x) };
+            let ratio = num.div(den, w + 2).unwrap().to_fixed(w);
+            let (angle, err) = atan_fixed(&ratio, w);
+
+            // An error of 1.25 unit on the ratio, and of 2 units on pi
+            let pi = pi_fixed::<T>(w);
+            let y = match (swap, x_negative, y_negative) {
+                (false, false, _) => angle,
+                (false, true, false) => angle + pi,
+                (false, true, true) => angle - pi,
+                (true, _, false) => (pi >> 1) - angle,
+                (true, _, true) => -(pi >> 1) - angle,
+            };
+            (y, err + 4, -(w as isize))
+        })
+    }
+}
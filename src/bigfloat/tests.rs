@@ -1,3 +1,5 @@
+use crate::errors::ParseError;
+use crate::rounding::RoundingMode;
 use crate::traits::Digit;
 use crate::BigUint;
 use typed_test_gen::test_with;
@@ -292,3 +294,339 @@ fn test_binary<T: Digit>() {
     assert_eq!(format!("{:b}", n2), ret2);
     assert_eq!(format!("{:b}", n3), ret3);
 }
+
+#[test_with(u32, u64)]
+fn scientific<T: Digit>() {
+    let one = BigFloat::<T>::from(vec![T::ONE]);
+    let three = BigFloat::<T>::from(vec![T::decomposition_from_u32(3)[0]]);
+    assert_eq!(format!("{:e}", &one >> 1), "5e-1");
+    assert_eq!(format!("{:e}", &three >> 2), "7.5e-1");
+    assert_eq!(format!("{:.0e}", &three >> 2), "8e-1");
+    assert_eq!(format!("{:e}", -(&one >> 3)), "-1.25e-1");
+    assert_eq!(format!("{:.1E}", -(&one >> 3)), "-1.2E-1");
+    assert_eq!(
+        format!("{:e}", &three << 100),
+        format!("{:e}", BigUint::<T>::from(3u32) << 100)
+    );
+
+    // Exact expansions, compared to the (exact) expansions of f64 at a high precision
+    for k in [1, 31, 32, 33, 64, 65, 100, 500] {
+        let x = &three >> k;
+        let f = 3.0 * 2f64.powi(-(k as i32));
+        assert_eq!(format!("{:.60e}", x), format!("{:.60e}", f));
+        assert_eq!(format!("{:.5e}", x), format!("{:.5e}", f));
+    }
+    assert_eq!(
+        format!("{:e}", &one >> 64),
+        "5.42101086242752217003726400434970855712890625e-20"
+    );
+}
+
+#[test_with(u32, u64)]
+fn display<T: Digit>() {
+    let three = BigFloat::<T>::from(3u32);
+    assert_eq!(format!("{}", &three >> 2), "0.75");
+    assert_eq!(format!("{:.1}", &three >> 2), "0.8");
+    assert_eq!(format!("{:.1}", &three >> 3), "0.4");
+    assert_eq!(format!("{:.3}", -(&three >> 2)), "-0.750");
+    assert_eq!(format!("{:+}", &three << 1), "+6");
+    assert_eq!(format!("{:.0}", BigFloat::<T>::from(19u32) >> 1), "10");
+    assert_eq!(
+        format!("{:>8.2}", BigFloat::<T>::from(1u32) >> 3),
+        "    0.12"
+    );
+    assert_eq!(
+        format!("{:.2}", BigFloat::<T>::from(1u32) << 64),
+        "18446744073709551616.00"
+    );
+    assert_eq!(format!("{}", BigFloat::<T>::default()), "0");
+
+    for f in [0.1f64, -2.5e-7, 1234.5678, 1e-30, 3e40] {
+        let x = BigFloat::<T>::try_from(f).unwrap();
+        assert_eq!(format!("{:.80}", x), format!("{:.80}", f));
+    }
+}
+
+#[test_with(u32, u64)]
+fn from_primitives<T: Digit>() {
+    assert_eq!(BigFloat::<T>::from(-5i32), -BigFloat::from(5u8));
+    assert_eq!(
+        BigFloat::<T>::from(u128::MAX),
+        BigFloat::from(BigUint::<T>::from(u128::MAX))
+    );
+    assert_eq!(BigFloat::<T>::from(7u64).precision(), None);
+
+    let half = BigFloat::<T>::try_from(-0.5f64).unwrap();
+    assert_eq!(half, -(BigFloat::from(1u32) >> 1));
+    assert_eq!(half.precision(), Some(53));
+    assert_eq!(
+        BigFloat::<T>::try_from(0.75f32).unwrap().precision(),
+        Some(24)
+    );
+
+    let smallest = BigFloat::<T>::try_from(f64::from_bits(1)).unwrap();
+    assert_eq!(smallest, BigFloat::from(1u32) >> 1074);
+    let largest = BigFloat::<T>::try_from(f64::MAX).unwrap();
+    assert_eq!(largest, BigFloat::from((1u64 << 53) - 1) << 971);
+    assert_eq!(BigFloat::<T>::try_from(-0f64).unwrap(), BigFloat::default());
+
+    assert!(BigFloat::<T>::try_from(f64::NAN).is_err());
+    assert!(BigFloat::<T>::try_from(f32::INFINITY).is_err());
+}
+
+#[test_with(u32, u64)]
+fn from_str<T: Digit>() {
+    let strings = [
+        "0.1",
+        "3.14159",
+        "-2.5e-7",
+        "123456789.987654321",
+        "1e22",
+        "1e23",
+        "+.5E+3",
+        "7.",
+        "4.9406564584124654e-300",
+        "179769313486231570814527423731704356798070567525844996598917476803157260780028538760589558632766878171540458953514382464234321326889464182768467546703537516986049910576551282076245490090389328944075868508455133942304583236903222948165808559332123348274797826204144723168738177180919299881250404026184124858368",
+    ];
+    for s in strings {
+        let x = BigFloat::<T>::from_str_with_precision(s, 53).unwrap();
+        assert_eq!(
+            x,
+            BigFloat::try_from(s.parse::<f64>().unwrap()).unwrap(),
+            "{s}"
+        );
+        assert_eq!(x.precision(), Some(53));
+    }
+
+    // Exact when representable, enough bits for the digits otherwise
+    let x = BigFloat::<T>::from("-12.375");
+    assert_eq!(x, -(BigFloat::from(99u32) >> 3));
+    assert_eq!(x.precision(), None);
+    assert_eq!(BigFloat::<T>::from("1e30").precision(), None);
+    assert_eq!(BigFloat::<T>::from("0.1").precision(), Some(53));
+    assert_eq!(
+        BigFloat::<T>::from("0.00012345678901234567890").precision(),
+        Some(68)
+    );
+    assert_eq!(BigFloat::<T>::from("-0.0e5"), BigFloat::default());
+    assert_eq!(BigFloat::<T>::from("-.5"), -(BigFloat::from(1u32) >> 1));
+    assert_eq!(BigFloat::<T>::from("+5."), BigFloat::from(5u32));
+
+    for (s, c, position) in [
+        ("1.2.3", '.', 3),
+        ("1e", 'e', 1),
+        ("1e+", 'e', 1),
+        ("abc", 'a', 0),
+        ("-.e1", 'e', 2),
+        ("12 ", ' ', 2),
+        ("1e99999999999999999999", '9', 8),
+        ("1e999999999999", '9', 8),
+        ("-1.5e-1000001", '1', 12),
+    ] {
+        let e = s.parse::<BigFloat<T>>().unwrap_err();
+        assert_eq!(
+            e,
+            ParseError::UnexpectedCharacter {
+                character: c,
+                position
+            },
+            "{s}"
+        );
+    }
+    for s in ["", ".", "+", "-", "-."] {
+        assert_eq!(s.parse::<BigFloat<T>>(), Err(ParseError::NoDigits), "{s}");
+    }
+
+    // A zero can have any exponent, without computing its power of five
+    for s in ["0e-99999999999", "-0.00e99999999999999999999"] {
+        assert_eq!(BigFloat::<T>::from(s), BigFloat::default());
+        assert_eq!(
+            BigFloat::<T>::from_str_with_precision(s, 10).unwrap(),
+            BigFloat::default()
+        );
+    }
+}
+
+#[test_with(u32, u64)]
+fn with_precision<T: Digit>() {
+    let n = BigFloat::<T>::from(0b1011_0111u32);
+    assert_eq!(n.clone().with_precision(8), n);
+    assert_eq!(n.clone().with_precision(4), BigFloat::from(0b1011_0000u32));
+    assert_eq!(n.clone().with_precision(3), BigFloat::from(0b1100_0000u32));
+    assert_eq!(n.clone().with_precision(1), BigFloat::from(0b1000_0000u32));
+    assert_eq!((-&n).with_precision(2), -BigFloat::from(0b1100_0000u32));
+
+    // Ties to even
+    assert_eq!(
+        BigFloat::<T>::from(0b101u32).with_precision(2),
+        BigFloat::from(0b100u32)
+    );
+    assert_eq!(
+        BigFloat::<T>::from(0b111u32).with_precision(2),
+        BigFloat::from(0b1000u32)
+    );
+
+    let x = (BigFloat::<T>::from(1u32) << 200) - BigFloat::from(1u32);
+    assert_eq!(x.with_precision(100), BigFloat::from(1u32) << 200);
+}
+
+/// Rounded sums and products against the rounding of the exact results
+#[test_with(u32, u64)]
+fn rounded_operations<T: Digit>() {
+    let mut values = vec![BigFloat::<T>::default()];
+    for (m, k) in [
+        (1u64, 0isize),
+        (3, -1000),
+        (0x1234_5678_9abc, 7),
+        (1023, -20),
+        (1 << 40, 0),
+    ] {
+        for shift in [-(T::NB_BITS as isize) - 3, -1, 0, 1, 63, 64, 65] {
+            let x = BigFloat::from(m).mul_pow2(k + shift);
+            values.push(-&x);
+            values.push(x);
+        }
+    }
+    values.push((BigFloat::from(1u32) << 64) - BigFloat::from(1u32));
+    values.push(BigFloat::from(1u32) << 64);
+
+    for a in &values {
+        for b in &values {
+            for precision in [1, 2, 10, 53, 64, 100] {
+                let sum = (a + b).with_precision(precision);
+                let got = a.add(b, precision);
+                assert_eq!(got, sum, "{a:?} + {b:?} ({precision})");
+                assert_eq!(got.precision(), Some(precision));
+                assert_eq!(a.sub(b, precision), (a - b).with_precision(precision));
+                assert_eq!(a.mul(b, precision), (a * b).with_precision(precision));
+            }
+        }
+    }
+}
+
+#[test_with(u32, u64)]
+fn operators_with_precision<T: Digit>() {
+    let one = BigFloat::<T>::from(1u32);
+    let tiny = &one >> 100;
+
+    let a = one.clone().with_precision(10);
+    assert_eq!(&a + &tiny, one);
+    assert_eq!((&a + &tiny).precision(), Some(10));
+    assert_eq!(&tiny + a.clone(), one);
+    assert_eq!(&a - &tiny, one);
+    assert_eq!((&one - &tiny).precision(), None);
+
+    let b = one.clone().with_precision(200);
+    assert_eq!(&a + &b + &tiny, BigFloat::from(2u32) + &tiny);
+    assert_eq!((&b * &a).precision(), Some(200));
+
+    let mut c = BigFloat::<T>::from(255u32).with_precision(4);
+    assert_eq!(c, BigFloat::from(256u32));
+    c += T::ONE;
+    assert_eq!(c, BigFloat::from(256u32));
+    c -= T::ONE;
+    assert_eq!(c, BigFloat::from(256u32));
+    c *= T::decomposition_from_u32(17)[0];
+    assert_eq!(c, BigFloat::from(4096u32));
+    c += &tiny;
+    assert_eq!(c, BigFloat::from(4096u32));
+}
+
+#[test_with(u32, u64)]
+fn precision_with_rounding<T: Digit>() {
+    // Below the half of the last kept bit
+    let n = BigFloat::<T>::from(0b1011_0100u32) >> 4;
+    for (mode, positive, negative) in [
+        (RoundingMode::NearestEven, 0b1011u32, 0b1011u32),
+        (RoundingMode::NearestAway, 0b1011, 0b1011),
+        (RoundingMode::TowardZero, 0b1011, 0b1011),
+        (RoundingMode::Floor, 0b1011, 0b1100),
+        (RoundingMode::Ceil, 0b1100, 0b1011),
+    ] {
+        let mut x = n.clone();
+        assert!(!x.set_precision_with_rounding(4, mode));
+        assert_eq!(x, BigFloat::from(positive), "{mode:?}");
+        let mut x = -&n;
+        assert!(!x.set_precision_with_rounding(4, mode));
+        assert_eq!(x, -BigFloat::from(negative), "{mode:?}");
+    }
+
+    // Ties
+    let tie = BigFloat::<T>::from(0b1010_1u32) >> 1;
+    for (mode, positive, negative) in [
+        (RoundingMode::NearestEven, 0b1010u32, 0b1010u32),
+        (RoundingMode::NearestAway, 0b1011, 0b1011),
+        (RoundingMode::TowardZero, 0b1010, 0b1010),
+        (RoundingMode::Floor, 0b1010, 0b1011),
+        (RoundingMode::Ceil, 0b1011, 0b1010),
+    ] {
+        let mut x = tie.clone();
+        x.set_precision_with_rounding(4, mode);
+        assert_eq!(x, BigFloat::from(positive), "{mode:?}");
+        let mut x = -&tie;
+        x.set_precision_with_rounding(4, mode);
+        assert_eq!(x, -BigFloat::from(negative), "{mode:?}");
+    }
+
+    let mut x = BigFloat::<T>::from(0b1010_0000u32) >> 100;
+    assert!(x.set_precision_with_rounding(3, RoundingMode::Ceil));
+    assert_eq!(x.precision(), Some(3));
+}
+
+#[test_with(u32, u64)]
+fn to_floats<T: Digit>() {
+    let one = BigFloat::<T>::from(1u32);
+    let x = &one + (&one >> 60);
+    assert_eq!(
+        x.to_f64_with_rounding(RoundingMode::NearestEven),
+        (1.0, false)
+    );
+    assert_eq!(
+        x.to_f64_with_rounding(RoundingMode::Ceil),
+        (1.0 + f64::EPSILON, false)
+    );
+    assert_eq!(
+        (-&x).to_f64_with_rounding(RoundingMode::Floor).0,
+        -1.0 - f64::EPSILON
+    );
+    assert_eq!((-&x).to_f64_with_rounding(RoundingMode::Ceil).0, -1.0);
+    assert_eq!(f32::from(&x), 1.0);
+
+    // Subnormals, underflow and overflow
+    let tiny = &one >> 1080;
+    assert_eq!(f64::from(&tiny), 0.0);
+    assert_eq!(
+        tiny.to_f64_with_rounding(RoundingMode::Ceil).0,
+        f64::from_bits(1)
+    );
+    assert_eq!(f64::from(&(&one >> 1074)), f64::from_bits(1));
+    assert_eq!(
+        f64::from(&(BigFloat::<T>::from(3u32) >> 1075)),
+        f64::from_bits(2)
+    );
+    let huge = &one << 1024;
+    assert_eq!(f64::from(&huge), f64::INFINITY);
+    assert_eq!(
+        huge.to_f64_with_rounding(RoundingMode::TowardZero).0,
+        f64::MAX
+    );
+    assert_eq!(f32::from(&-huge), f32::NEG_INFINITY);
+
+    for f in [0.1f64, -2.5e-7, 1e300, -4.9e-324, 123456.789] {
+        let x = BigFloat::<T>::try_from(f).unwrap();
+        assert_eq!(x.to_f64_with_rounding(RoundingMode::Floor), (f, true));
+        assert_eq!(f32::from(&x), f as f32);
+    }
+
+    // Rounded division with directed modes
+    let three = BigFloat::<T>::from(3u32);
+    let floor = one.div_round(&three, 53, RoundingMode::Floor).unwrap();
+    let ceil = one.div_round(&three, 53, RoundingMode::Ceil).unwrap();
+    assert_eq!(f64::from(&floor), 1.0 / 3.0);
+    assert_eq!(&ceil - &floor, &one >> 54);
+    let eight = BigFloat::<T>::from(8u32);
+    assert_eq!(
+        eight.div_round(&three, 2, RoundingMode::Ceil).unwrap(),
+        BigFloat::from(3u32)
+    );
+}
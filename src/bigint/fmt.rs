@@ -1,64 +1,83 @@
 //! (private) fmt: private module containing implementation of traits
 //! pertaining to I/O formatting.
 
+use crate::biguint::fmt::exp;
+use crate::biguint::ops::to_radix;
 use crate::traits::Digit;
 use crate::BigInt;
 
+impl<T: Digit> BigInt<T> {
+    /// Writes the integer in the given radix, with a leading minus sign if it
+    /// is negative, and lowercase letters for the digits above 9.
+    ///
+    /// ```
+    /// use twibint::BigInt;
+    ///
+    /// assert_eq!(BigInt::<u64>::from(-255).to_str_radix(16), "-ff");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the radix is not in the range from 2 to 36.
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        let mut ret = match self.is_sign_negative() {
+            true => "-".to_string(),
+            false => "".to_string(),
+        };
+        ret.push_str(&self.uint.to_str_radix(radix));
+        ret
+    }
+}
+
 impl<T: Digit> std::fmt::LowerExp for BigInt<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let val = f64::from(self);
-        std::fmt::LowerExp::fmt(&val, f)
+        exp(f, !self.is_sign_negative(), &self.uint, 0, false)
     }
 }
 impl<T: Digit> std::fmt::UpperExp for BigInt<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let val = f64::from(self);
-        std::fmt::UpperExp::fmt(&val, f)
+        exp(f, !self.is_sign_negative(), &self.uint, 0, true)
+    }
+}
+
+impl<T: Digit> std::fmt::Display for BigInt<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad_integral(!self.is_sign_negative(), "", &to_radix(&self.uint, 10))
     }
 }
 
 impl<T: Digit> std::fmt::LowerHex for BigInt<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut ret = match self.sign {
-            true => "".to_string(),
-            false => "-".to_string(),
-        };
-        ret.push_str(&format!("{:x}", self.uint));
-        write!(f, "{}", ret)
+        f.pad_integral(!self.is_sign_negative(), "0x", &to_radix(&self.uint, 16))
     }
 }
 impl<T: Digit> std::fmt::UpperHex for BigInt<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut ret = match self.sign {
-            true => "".to_string(),
-            false => "-".to_string(),
-        };
-        ret.push_str(&format!("{:X}", self.uint));
-        write!(f, "{}", ret)
+        let digits = to_radix(&self.uint, 16).to_uppercase();
+        f.pad_integral(!self.is_sign_negative(), "0x", &digits)
     }
 }
 
-impl<T: Digit> std::fmt::Display for BigInt<T> {
+impl<T: Digit> std::fmt::Octal for BigInt<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", String::from(self))
+        f.pad_integral(!self.is_sign_negative(), "0o", &to_radix(&self.uint, 8))
     }
 }
 
+impl<T: Digit> std::fmt::Binary for BigInt<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad_integral(!self.is_sign_negative(), "0b", &to_radix(&self.uint, 2))
+    }
+}
+
+/// Writes the sign, then all the bits of the digits, leading zeros included
 pub(crate) fn bin<T: Digit>(
     sign: bool,
     val: &[T],
     f: &mut std::fmt::Formatter<'_>,
 ) -> std::fmt::Result {
-    let ret = match sign {
-        true => "".to_string(),
-        false => "-".to_string(),
-    };
-    write!(f, "{}", ret)?;
-    crate::biguint::fmt::bin(val, f)
-}
-
-impl<T: Digit> std::fmt::Binary for BigInt<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        bin(self.sign, &self.uint.val, f)
+    if !sign {
+        write!(f, "-")?;
     }
+    crate::biguint::fmt::bin(val, f)
 }
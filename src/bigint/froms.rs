@@ -3,37 +3,115 @@
 //! These implementations are meant to be the main way to construct a BigInt,
 //! or export its value into another type.
 
+use crate::biguint::froms::parse_literal;
 use crate::errors::FromFloatError;
-use crate::errors::UnexpectedCharacterError;
+use crate::errors::ParseError;
+use crate::errors::TryFromIntError;
+use crate::rounding::{RoundingMode, F32, F64};
 use crate::traits::Digit;
 use crate::{BigInt, BigUint};
 
-impl<T: Digit> From<i32> for BigInt<T> {
-    fn from(val: i32) -> BigInt<T> {
-        BigInt::<T> {
-            uint: BigUint::<T>::from(TryInto::<u32>::try_into(val.abs()).unwrap()),
-            sign: val.is_positive(),
+/// Conversions from the primitives, through the absolute value
+macro_rules! from_primitive {
+    ($($t:ty),*) => {$(
+        impl<T: Digit> From<$t> for BigInt<T> {
+            fn from(val: $t) -> BigInt<T> {
+                BigInt::<T> {
+                    uint: BigUint::<T>::from(val.unsigned_abs()),
+                    sign: val >= 0,
+                }
+            }
         }
-    }
+    )*};
 }
-impl<T: Digit> From<u32> for BigInt<T> {
-    fn from(val: u32) -> BigInt<T> {
-        BigInt::<T>::from(BigUint::<T>::from(val))
-    }
+from_primitive!(i8, i16, i32, i64, i128, isize);
+
+macro_rules! from_unsigned_primitive {
+    ($($t:ty),*) => {$(
+        impl<T: Digit> From<$t> for BigInt<T> {
+            fn from(val: $t) -> BigInt<T> {
+                BigInt::<T>::from(BigUint::<T>::from(val))
+            }
+        }
+    )*};
 }
-impl<T: Digit> From<u64> for BigInt<T> {
-    fn from(val: u64) -> BigInt<T> {
-        BigInt::<T>::from(BigUint::<T>::from(val))
-    }
+from_unsigned_primitive!(u8, u16, u32, u64, u128, usize);
+
+/// Conversions to the unsigned primitives fail on negative integers
+macro_rules! try_into_unsigned {
+    ($($t:ty),*) => {$(
+        impl<T: Digit> TryFrom<&BigInt<T>> for $t {
+            type Error = TryFromIntError;
+            fn try_from(int: &BigInt<T>) -> Result<$t, Self::Error> {
+                match int.is_sign_negative() {
+                    true => Err(TryFromIntError::Negative),
+                    false => <$t>::try_from(&int.uint),
+                }
+            }
+        }
+    )*};
 }
-impl<T: Digit> From<i64> for BigInt<T> {
-    fn from(val: i64) -> BigInt<T> {
-        BigInt::<T> {
-            uint: BigUint::<T>::from(TryInto::<u64>::try_into(val.abs()).unwrap()),
-            sign: val.is_positive(),
+try_into_unsigned!(u8, u16, u32, u64, u128, usize);
+
+/// Conversions to the signed primitives go through i128
+macro_rules! try_into_signed {
+    ($($t:ty => $error:ident),*) => {$(
+        impl<T: Digit> TryFrom<&BigInt<T>> for $t {
+            type Error = TryFromIntError;
+            fn try_from(int: &BigInt<T>) -> Result<$t, Self::Error> {
+                let abs = u128::try_from(&int.uint).map_err(|_| TryFromIntError::$error)?;
+                let val = match int.is_sign_negative() {
+                    true => 0i128.checked_sub_unsigned(abs),
+                    false => i128::try_from(abs).ok(),
+                };
+                val.and_then(|val| val.try_into().ok())
+                    .ok_or(TryFromIntError::$error)
+            }
         }
-    }
+    )*};
 }
+try_into_signed!(
+    i8 => ToInt8Overflow,
+    i16 => ToInt16Overflow,
+    i32 => ToInt32Overflow,
+    i64 => ToInt64Overflow,
+    i128 => ToInt128Overflow,
+    isize => ToIsizeOverflow
+);
+
+/// Explicitly truncating conversions to the primitives, in two's complement
+macro_rules! to_wrapping {
+    ($($name:ident -> $t:ty),*) => {
+        impl<T: Digit> BigInt<T> {$(
+            #[doc = concat!(
+                "Converts to a `", stringify!($t), "`, keeping only the lowest bits ",
+                "of the two's complement representation of the integer, like an `as` ",
+                "cast between primitive integers."
+            )]
+            #[inline]
+            pub fn $name(&self) -> $t {
+                match self.sign {
+                    true => self.uint.low_u128() as $t,
+                    false => self.uint.low_u128().wrapping_neg() as $t,
+                }
+            }
+        )*}
+    };
+}
+to_wrapping!(
+    to_u8_wrapping -> u8,
+    to_u16_wrapping -> u16,
+    to_u32_wrapping -> u32,
+    to_u64_wrapping -> u64,
+    to_u128_wrapping -> u128,
+    to_usize_wrapping -> usize,
+    to_i8_wrapping -> i8,
+    to_i16_wrapping -> i16,
+    to_i32_wrapping -> i32,
+    to_i64_wrapping -> i64,
+    to_i128_wrapping -> i128,
+    to_isize_wrapping -> isize
+);
 
 impl<T: Digit> From<BigUint<T>> for BigInt<T> {
     fn from(val: BigUint<T>) -> BigInt<T> {
@@ -52,51 +130,102 @@ impl<T: Digit> From<Vec<T>> for BigInt<T> {
 
 impl<T: Digit> From<&BigInt<T>> for f64 {
     fn from(int: &BigInt<T>) -> f64 {
-        match int.sign {
-            true => f64::from(&int.uint),
-            false => -f64::from(&int.uint),
-        }
+        int.to_f64_with_rounding(RoundingMode::NearestEven).0
     }
 }
 
 impl<T: Digit> From<&BigInt<T>> for f32 {
     fn from(int: &BigInt<T>) -> f32 {
-        match int.sign {
-            true => f32::from(&int.uint),
-            false => -f32::from(&int.uint),
-        }
+        let negative = int.is_sign_negative();
+        let (bits, _) = F32.round(&int.uint, 0, false, negative, RoundingMode::NearestEven);
+        f32::from_bits(bits as u32 | (negative as u32) << 31)
     }
 }
 
-fn car_cdr(s: &str) -> (char, &str) {
-    assert!(s.len() > 0);
+impl<T: Digit> BigInt<T> {
+    /// Converts to the `f64` obtained by rounding with the given mode, and
+    /// tells whether the conversion is exact. Integers too large for a `f64`
+    /// give an infinity, or the largest finite `f64` of the same sign when
+    /// rounding toward zero, or away from the infinity for `Floor` and `Ceil`.
+    ///
+    /// ```
+    /// use twibint::{BigInt, RoundingMode};
+    ///
+    /// let n = -BigInt::<u64>::from((1u64 << 53) + 1);
+    /// assert_eq!(n.to_f64_with_rounding(RoundingMode::Floor), (-9007199254740994.0, false));
+    /// assert_eq!(n.to_f64_with_rounding(RoundingMode::TowardZero), (-9007199254740992.0, false));
+    /// ```
+    pub fn to_f64_with_rounding(&self, mode: RoundingMode) -> (f64, bool) {
+        let negative = self.is_sign_negative();
+        let (bits, exact) = F64.round(&self.uint, 0, false, negative, mode);
+        (f64::from_bits(bits | (negative as u64) << 63), exact)
+    }
+}
 
-    for i in 1..5 {
-        let r = s.get(0..i);
-        match r {
-            Some(x) => return (x.chars().next().unwrap(), &s[i..]),
-            None => (),
+impl<T: Digit> BigInt<T> {
+    /// Reads an integer written in the given radix, with an optional leading
+    /// minus sign, and letters of any case for the digits above 9. A string
+    /// without digits, empty or only a sign, is an error.
+    ///
+    /// ```
+    /// use twibint::{BigInt, ParseError};
+    ///
+    /// let n = BigInt::<u64>::from_str_radix("-ff", 16).unwrap();
+    /// assert_eq!(n, BigInt::from(-255));
+    ///
+    /// let e = BigInt::<u64>::from_str_radix("-12a", 10).unwrap_err();
+    /// assert_eq!(e, ParseError::UnexpectedCharacter { character: 'a', position: 3 });
+    ///
+    /// assert_eq!(BigInt::<u64>::from_str_radix("-", 10), Err(ParseError::NoDigits));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the radix is not in the range from 2 to 36.
+    pub fn from_str_radix(s: &str, radix: u32) -> Result<BigInt<T>, ParseError> {
+        match s.strip_prefix('-') {
+            Some(abs) => match BigUint::from_str_radix(abs, radix) {
+                Ok(uint) => Ok(BigInt { uint, sign: false }),
+                Err(ParseError::UnexpectedCharacter {
+                    character,
+                    position,
+                }) => Err(ParseError::UnexpectedCharacter {
+                    character,
+                    position: position + 1,
+                }),
+                Err(e) => Err(e),
+            },
+            None => Ok(BigInt::from(BigUint::from_str_radix(s, radix)?)),
         }
     }
+}
 
-    (s[0..0].chars().next().unwrap(), s)
+impl<T: Digit> BigInt<T> {
+    /// Reads an integer with the rules of Rust and Python literals, like
+    /// Python's `int(s, 0)`: surrounding whitespace is ignored, a leading `+`
+    /// or `-` is allowed, `0x`, `0o` and `0b` prefixes select the radix, and
+    /// digits can be grouped with underscores. As in Python, a string without
+    /// digits is an error, and so is a nonzero decimal with leading zeros.
+    ///
+    /// ```
+    /// use twibint::{BigInt, ParseError};
+    ///
+    /// let n = BigInt::<u64>::from_str_lenient("\t-0b1010_1010 ").unwrap();
+    /// assert_eq!(n, BigInt::from(-170));
+    ///
+    /// let e = BigInt::<u64>::from_str_lenient("-0x").unwrap_err();
+    /// assert_eq!(e, ParseError::UnexpectedCharacter { character: 'x', position: 2 });
+    /// ```
+    pub fn from_str_lenient(s: &str) -> Result<BigInt<T>, ParseError> {
+        let (sign, uint) = parse_literal(s)?;
+        Ok(BigInt { uint, sign })
+    }
 }
 
 impl<T: Digit> std::str::FromStr for BigInt<T> {
-    type Err = UnexpectedCharacterError;
+    type Err = ParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.len() == 0 {
-            return Ok(BigInt::<T>::default());
-        }
-
-        let (r, s2) = car_cdr(s);
-        match r {
-            '-' => Ok(BigInt::<T> {
-                uint: s2.parse::<BigUint<T>>()?,
-                sign: false,
-            }),
-            _ => Ok(BigInt::<T>::from(s.parse::<BigUint<T>>()?)),
-        }
+        BigInt::from_str_radix(s, 10)
     }
 }
 
@@ -162,3 +291,83 @@ impl<T: Digit> TryFrom<f32> for BigInt<T> {
         }
     }
 }
+
+/// Two's complement, in place, of little endian bytes
+fn negate_bytes(bytes: &mut [u8]) {
+    let mut carry = true;
+    for b in bytes.iter_mut() {
+        (*b, carry) = (!*b).overflowing_add(carry as u8);
+    }
+}
+
+impl<T: Digit> BigInt<T> {
+    /// Returns the bytes of the two's complement representation of the
+    /// integer, least significant first, with as few bytes as possible.
+    ///
+    /// ```
+    /// use twibint::BigInt;
+    ///
+    /// assert_eq!(BigInt::<u64>::from(-129).to_signed_bytes_le(), vec![0x7f, 0xff]);
+    /// assert_eq!(BigInt::<u64>::from(128).to_signed_bytes_le(), vec![0x80, 0x00]);
+    /// ```
+    pub fn to_signed_bytes_le(&self) -> Vec<u8> {
+        let mut ret = self.uint.to_bytes_le();
+        if self.is_sign_negative() {
+            negate_bytes(&mut ret);
+            if ret.last().unwrap() & 0x80 == 0 {
+                ret.push(0xff);
+            }
+        } else if ret.last().unwrap() & 0x80 != 0 {
+            ret.push(0);
+        }
+        ret
+    }
+
+    /// Returns the bytes of the two's complement representation of the
+    /// integer, most significant first, with as few bytes as possible.
+    ///
+    /// ```
+    /// use twibint::BigInt;
+    ///
+    /// assert_eq!(BigInt::<u64>::from(-129).to_signed_bytes_be(), vec![0xff, 0x7f]);
+    /// ```
+    pub fn to_signed_bytes_be(&self) -> Vec<u8> {
+        let mut ret = self.to_signed_bytes_le();
+        ret.reverse();
+        ret
+    }
+
+    /// Builds an integer from the bytes of its two's complement
+    /// representation, least significant first. An empty slice gives zero.
+    ///
+    /// ```
+    /// use twibint::BigInt;
+    ///
+    /// assert_eq!(BigInt::<u64>::from_signed_bytes_le(&[0x7f, 0xff]), BigInt::from(-129));
+    /// assert_eq!(BigInt::<u64>::from_signed_bytes_le(&[0xff, 0xff]), BigInt::from(-1));
+    /// ```
+    pub fn from_signed_bytes_le(bytes: &[u8]) -> BigInt<T> {
+        match bytes.last() {
+            Some(b) if b & 0x80 != 0 => {
+                let mut bytes = bytes.to_vec();
+                negate_bytes(&mut bytes);
+                -BigInt::from(BigUint::from_bytes_le(&bytes))
+            }
+            _ => BigInt::from(BigUint::from_bytes_le(bytes)),
+        }
+    }
+
+    /// Builds an integer from the bytes of its two's complement
+    /// representation, most significant first. An empty slice gives zero.
+    ///
+    /// ```
+    /// use twibint::BigInt;
+    ///
+    /// assert_eq!(BigInt::<u64>::from_signed_bytes_be(&[0xff, 0x7f]), BigInt::from(-129));
+    /// ```
+    pub fn from_signed_bytes_be(bytes: &[u8]) -> BigInt<T> {
+        let mut bytes = bytes.to_vec();
+        bytes.reverse();
+        BigInt::from_signed_bytes_le(&bytes)
+    }
+}
@@ -11,8 +11,6 @@ pub(crate) mod ops;
 #[cfg(test)]
 mod test;
 
-// TODO: implement ilog2 and that sort of things
-
 /// Representation of an signed integer with an infinite number of bits.
 ///
 /// The internal representation has 2 members: a BigUint for the absolute value, and
@@ -56,6 +54,30 @@ impl<T: Digit> BigInt<T> {
         self.uint != BigUint::default() && !self.sign
     }
 
+    /// Returns the number of zeros below the lowest one in the binary
+    /// representation (which is the same for the two's complement), or None for zero.
+    ///
+    /// ```
+    /// use twibint::BigInt;
+    /// assert_eq!(BigInt::<u64>::from(-40).trailing_zeros(), Some(3));
+    /// ```
+    #[inline]
+    pub fn trailing_zeros(&self) -> Option<usize> {
+        self.uint.trailing_zeros()
+    }
+
+    /// Returns the number of ones in the binary representation of the absolute
+    /// value, like the `bit_count` method of Python integers.
+    ///
+    /// ```
+    /// use twibint::BigInt;
+    /// assert_eq!(BigInt::<u64>::from(-7).count_ones(), 3);
+    /// ```
+    #[inline]
+    pub fn count_ones(&self) -> usize {
+        self.uint.count_ones()
+    }
+
     /// Copies data from other into self, keeping self's allocation if possible
     pub fn copy_from(&mut self, other: &Self) {
         self.uint.copy_from(&other.uint);
@@ -1,8 +1,43 @@
+use core::cmp::Ordering;
 use core::ops::{Div, DivAssign, Rem, RemAssign};
 
+use crate::rounding::RoundingMode;
 use crate::traits::{Digit, DivisionResult, RemDiv};
 use crate::{BigInt, BigUint};
 
+impl<T: Digit> BigInt<T> {
+    /// Returns the quotient of self by d, rounded to an integer with the
+    /// given mode. Fails on a division by zero.
+    ///
+    /// ```
+    /// use twibint::{BigInt, RoundingMode};
+    ///
+    /// let n = BigInt::<u64>::from(-7);
+    /// let d = BigInt::<u64>::from(2);
+    /// assert_eq!(n.div_round(&d, RoundingMode::NearestEven).unwrap(), BigInt::from(-4));
+    /// assert_eq!(n.div_round(&d, RoundingMode::TowardZero).unwrap(), BigInt::from(-3));
+    /// assert_eq!(n.div_round(&d, RoundingMode::Ceil).unwrap(), BigInt::from(-3));
+    /// ```
+    pub fn div_round(&self, d: &BigInt<T>, mode: RoundingMode) -> DivisionResult<BigInt<T>> {
+        let (mut q, r) = self.uint.rem_div(&d.uint)?;
+        let negative = self.is_sign_negative() != d.is_sign_negative();
+
+        let (half, lower) = match (&r << 1).cmp(&d.uint) {
+            Ordering::Less => (false, !r.is_zero()),
+            Ordering::Equal => (true, false),
+            Ordering::Greater => (true, true),
+        };
+        if mode.round_up(negative, q.bit(0), half, lower) {
+            q += T::ONE;
+        }
+
+        Ok(BigInt::<T> {
+            sign: !negative || q.is_zero(),
+            uint: q,
+        })
+    }
+}
+
 impl<T: Digit> RemDiv<T> for BigInt<T> {
     type DivOutput = BigInt<T>;
     type RemOutput = T;
@@ -0,0 +1,56 @@
+use crate::traits::Digit;
+use crate::BigInt;
+
+impl<T: Digit> BigInt<T> {
+    /// Returns the greatest common divisor of `self` and `other`, which is
+    /// always non-negative. By convention, `gcd(n, 0) = |n|`.
+    ///
+    /// ```
+    /// use twibint::BigInt;
+    ///
+    /// let a = BigInt::<u64>::from(-84);
+    /// let b = BigInt::<u64>::from(60);
+    /// assert_eq!(a.gcd(&b), BigInt::from(12));
+    /// ```
+    pub fn gcd(&self, other: &BigInt<T>) -> BigInt<T> {
+        BigInt::from(self.uint.gcd(&other.uint))
+    }
+
+    /// Returns the least common multiple of `self` and `other`, which is
+    /// always non-negative. By convention, `lcm(n, 0) = 0`.
+    ///
+    /// ```
+    /// use twibint::BigInt;
+    ///
+    /// let a = BigInt::<u64>::from(-84);
+    /// let b = BigInt::<u64>::from(60);
+    /// assert_eq!(a.lcm(&b), BigInt::from(420));
+    /// ```
+    pub fn lcm(&self, other: &BigInt<T>) -> BigInt<T> {
+        BigInt::from(self.uint.lcm(&other.uint))
+    }
+
+    /// Returns `(g, x, y)` where `g` is the (non-negative) greatest common divisor
+    /// of `self` and `other`, and `x` and `y` are Bézout coefficients such that
+    /// `self * x + other * y = g`.
+    ///
+    /// ```
+    /// use twibint::BigInt;
+    ///
+    /// let a = BigInt::<u64>::from(-240);
+    /// let b = BigInt::<u64>::from(46);
+    /// let (g, x, y) = a.extended_gcd(&b);
+    /// assert_eq!(g, BigInt::from(2));
+    /// assert_eq!(a * x + b * y, g);
+    /// ```
+    pub fn extended_gcd(&self, other: &BigInt<T>) -> (BigInt<T>, BigInt<T>, BigInt<T>) {
+        let (g, mut x, mut y) = self.uint.extended_gcd(&other.uint);
+        if !self.sign {
+            x = -x;
+        }
+        if !other.sign {
+            y = -y;
+        }
+        (BigInt::from(g), x, y)
+    }
+}
@@ -0,0 +1,99 @@
+use crate::traits::Digit;
+use crate::{BigInt, BigUint};
+
+impl<T: Digit> BigInt<T> {
+    /// Returns the base 2 logarithm of `self`, rounded down.
+    ///
+    /// This will panic if `self` is not strictly positive.
+    ///
+    /// ```
+    /// use twibint::BigInt;
+    ///
+    /// assert_eq!(BigInt::<u64>::from(1024).ilog2(), 10);
+    /// ```
+    pub fn ilog2(&self) -> usize {
+        self.checked_ilog2()
+            .expect("The logarithm is only defined for positive numbers")
+    }
+
+    /// Returns the base 2 logarithm of `self`, rounded down, or None if
+    /// `self` is not strictly positive.
+    ///
+    /// ```
+    /// use twibint::BigInt;
+    ///
+    /// assert_eq!(BigInt::<u64>::from(1024).checked_ilog2(), Some(10));
+    /// assert_eq!(BigInt::<u64>::from(-1024).checked_ilog2(), None);
+    /// ```
+    pub fn checked_ilog2(&self) -> Option<usize> {
+        self.is_sign_positive()
+            .then(|| self.uint.checked_ilog2())
+            .flatten()
+    }
+
+    /// Returns the base 10 logarithm of `self`, rounded down. This is exact,
+    /// whatever the size of `self`.
+    ///
+    /// This will panic if `self` is not strictly positive.
+    ///
+    /// ```
+    /// use twibint::BigInt;
+    ///
+    /// assert_eq!(BigInt::<u64>::from(999).ilog10(), 2);
+    /// ```
+    pub fn ilog10(&self) -> usize {
+        self.checked_ilog10()
+            .expect("The logarithm is only defined for positive numbers")
+    }
+
+    /// Returns the base 10 logarithm of `self`, rounded down, or None if
+    /// `self` is not strictly positive.
+    ///
+    /// ```
+    /// use twibint::BigInt;
+    ///
+    /// assert_eq!(BigInt::<u64>::from(1000).checked_ilog10(), Some(3));
+    /// assert_eq!(BigInt::<u64>::from(-1000).checked_ilog10(), None);
+    /// ```
+    pub fn checked_ilog10(&self) -> Option<usize> {
+        self.is_sign_positive()
+            .then(|| self.uint.checked_ilog10())
+            .flatten()
+    }
+
+    /// Returns the logarithm of `self` in the given base, rounded down.
+    ///
+    /// This will panic if `self` is not strictly positive, or if `base` is
+    /// lower than 2.
+    ///
+    /// ```
+    /// use twibint::{BigInt, BigUint};
+    ///
+    /// let base = BigUint::<u64>::from(7u64);
+    /// assert_eq!(BigInt::<u64>::from(343).ilog(&base), 3);
+    /// ```
+    pub fn ilog(&self, base: &BigUint<T>) -> usize {
+        assert!(
+            base > &BigUint::new(T::ONE),
+            "The base of a logarithm has to be at least 2"
+        );
+        self.checked_ilog(base)
+            .expect("The logarithm is only defined for positive numbers")
+    }
+
+    /// Returns the logarithm of `self` in the given base, rounded down, or None
+    /// if `self` is not strictly positive or `base` lower than 2.
+    ///
+    /// ```
+    /// use twibint::{BigInt, BigUint};
+    ///
+    /// let base = BigUint::<u64>::from(7u64);
+    /// assert_eq!(BigInt::<u64>::from(343).checked_ilog(&base), Some(3));
+    /// assert_eq!(BigInt::<u64>::from(-343).checked_ilog(&base), None);
+    /// ```
+    pub fn checked_ilog(&self, base: &BigUint<T>) -> Option<usize> {
+        self.is_sign_positive()
+            .then(|| self.uint.checked_ilog(base))
+            .flatten()
+    }
+}
@@ -4,9 +4,13 @@
 mod addsub;
 mod bitwise;
 mod divrem;
+mod gcd;
+mod ilog;
+mod modpow;
 mod mul;
 mod neg;
 mod pow;
+mod root;
 mod shift;
 mod truediv;
 
@@ -0,0 +1,25 @@
+use crate::traits::{Digit, DivisionResult};
+use crate::{BigInt, BigUint};
+
+impl<T: Digit> BigInt<T> {
+    /// Returns `self` to the power `exp`, modulo `modulus`. The result is
+    /// always in the range `[0, modulus)`, even for a negative `self`.
+    ///
+    /// This will return an error if `modulus` is zero.
+    ///
+    /// ```
+    /// use twibint::{BigInt, BigUint};
+    ///
+    /// let n = BigInt::<u64>::from(-4);
+    /// let exp = BigUint::<u64>::from(13u64);
+    /// let modulus = BigUint::<u64>::from(497u64);
+    /// assert_eq!(n.modpow(&exp, &modulus).unwrap(), BigInt::from(52));
+    /// ```
+    pub fn modpow(&self, exp: &BigUint<T>, modulus: &BigUint<T>) -> DivisionResult<BigInt<T>> {
+        let mut ret = self.uint.modpow(exp, modulus)?;
+        if !self.sign && exp.bit(0) && !ret.is_zero() {
+            ret = modulus - &ret;
+        }
+        Ok(BigInt::from(ret))
+    }
+}
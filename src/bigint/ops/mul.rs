@@ -30,6 +30,35 @@ impl<T: Digit> BigInt<T> {
         self.uint._set_to_mul(a, b);
         self.sign = a_sign == b_sign;
     }
+
+    /// Returns the square of this integer, with a dedicated algorithm that is
+    /// faster than a multiplication
+    ///
+    /// ```
+    /// use twibint::BigInt;
+    ///
+    /// let n = BigInt::<u64>::from(-12i64);
+    /// assert_eq!(n.square(), BigInt::from(144i64));
+    /// ```
+    #[inline]
+    pub fn square(&self) -> BigInt<T> {
+        BigInt::from(self.uint.square())
+    }
+
+    /// Replaces this integer by its square
+    ///
+    /// ```
+    /// use twibint::BigInt;
+    ///
+    /// let mut n = BigInt::<u64>::from(-12i64);
+    /// n.square_assign();
+    /// assert_eq!(n, BigInt::from(144i64));
+    /// ```
+    #[inline]
+    pub fn square_assign(&mut self) {
+        self.uint.square_assign();
+        self.sign = true;
+    }
 }
 
 impl<T: Digit> MulAssign<T> for BigInt<T> {
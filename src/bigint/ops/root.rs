@@ -0,0 +1,82 @@
+use crate::biguint::ops::{is_perfect_power, nth_root};
+use crate::traits::Digit;
+use crate::BigInt;
+
+impl<T: Digit> BigInt<T> {
+    /// Returns the square root of `self`, rounded down, or None if `self`
+    /// is negative.
+    ///
+    /// ```
+    /// use twibint::BigInt;
+    ///
+    /// assert_eq!(BigInt::<u64>::from(99).checked_isqrt(), Some(BigInt::from(9)));
+    /// assert_eq!(BigInt::<u64>::from(-99).checked_isqrt(), None);
+    /// ```
+    pub fn checked_isqrt(&self) -> Option<BigInt<T>> {
+        self.checked_sqrt_rem().map(|(s, _)| s)
+    }
+
+    /// Returns `(s, r)` where `s` is the square root of `self` rounded down,
+    /// and `r = self - s * s` the remainder, or None if `self` is negative.
+    ///
+    /// ```
+    /// use twibint::BigInt;
+    ///
+    /// let (s, r) = BigInt::<u64>::from(99).checked_sqrt_rem().unwrap();
+    /// assert_eq!(s, BigInt::from(9));
+    /// assert_eq!(r, BigInt::from(18));
+    /// ```
+    pub fn checked_sqrt_rem(&self) -> Option<(BigInt<T>, BigInt<T>)> {
+        if self.is_sign_negative() {
+            return None;
+        }
+        let (s, r) = self.uint.sqrt_rem();
+        Some((BigInt::from(s), BigInt::from(r)))
+    }
+
+    /// Returns the `k`-th root of `self`, rounded towards zero, or None if `self`
+    /// is negative and `k` even.
+    ///
+    /// This will panic if `k` is zero.
+    ///
+    /// ```
+    /// use twibint::BigInt;
+    ///
+    /// assert_eq!(BigInt::<u64>::from(-1001).checked_nth_root(3), Some(BigInt::from(-10)));
+    /// assert_eq!(BigInt::<u64>::from(-1001).checked_nth_root(4), None);
+    /// ```
+    pub fn checked_nth_root(&self, k: usize) -> Option<BigInt<T>> {
+        assert!(k > 0, "The 0th root is not defined");
+        if self.is_sign_negative() && k.is_multiple_of(2) {
+            return None;
+        }
+        let mut ret = BigInt::from(nth_root(&self.uint, k));
+        ret.sign = self.sign;
+        Some(ret)
+    }
+
+    /// Returns true if `self` is the square of an integer.
+    ///
+    /// ```
+    /// use twibint::BigInt;
+    ///
+    /// assert!(BigInt::<u64>::from(144).is_perfect_square());
+    /// assert!(!BigInt::<u64>::from(-144).is_perfect_square());
+    /// ```
+    pub fn is_perfect_square(&self) -> bool {
+        !self.is_sign_negative() && self.uint.is_perfect_square()
+    }
+
+    /// Returns true if `self` is `x^k` for some integers `x` and `k > 1`.
+    /// By convention, this is true for 0, 1 and -1.
+    ///
+    /// ```
+    /// use twibint::BigInt;
+    ///
+    /// assert!(BigInt::<u64>::from(-243).is_perfect_power());
+    /// assert!(!BigInt::<u64>::from(-16).is_perfect_power());
+    /// ```
+    pub fn is_perfect_power(&self) -> bool {
+        is_perfect_power(&self.uint, self.is_sign_negative())
+    }
+}
@@ -1,3 +1,4 @@
+use crate::rounding::RoundingMode;
 use crate::traits::{Digit, Pow, TrueDiv};
 use crate::{BigInt, BigUint};
 
@@ -199,6 +200,18 @@ fn div_rem_2<T: Digit>() {
     assert_eq!(r, BigInt::<T>::from(-2));
 }
 
+#[test_with(u32, u64)]
+fn square<T: Digit>() {
+    let n = BigInt::<T>::from(-12i32);
+    assert_eq!(n.square(), BigInt::<T>::from(144i32));
+
+    let mut n = -BigInt::<T>::from(vec![T::MAX; 40]);
+    let expected = &n * &n.clone();
+    n.square_assign();
+    assert_eq!(n, expected);
+    assert!(n.sign);
+}
+
 #[test_with(u32, u64)]
 fn pow<T: Digit>() {
     let n = BigInt::<T>::from(-5i32);
@@ -226,6 +239,21 @@ fn pow<T: Digit>() {
     assert_eq!(n2, BigInt::<T>::from("-40564819207303340847894502572032"));
 }
 
+#[test_with(u32, u64)]
+fn truediv_signs<T: Digit>() {
+    let a = BigInt::<T>::from(-1);
+    let b = BigInt::<T>::from(3);
+    assert_eq!(a.truediv(&b).unwrap(), -1.0 / 3.0);
+    assert_eq!(b.truediv(&a).unwrap(), -3.0);
+    assert_eq!(a.truediv(&-&b).unwrap(), 1.0 / 3.0);
+    let zero = BigInt::<T>::default().truediv(&a).unwrap();
+    assert!(zero == 0.0 && zero.is_sign_negative());
+    let tiny = a
+        .truediv(&BigInt::from(BigUint::<T>::from(1u32) << 1074))
+        .unwrap();
+    assert_eq!(tiny, -f64::from_bits(1));
+}
+
 #[test_with(u32, u64)]
 fn truediv<T: Digit>() {
     let n1 = BigInt::<T>::from("123456678890123345567789");
@@ -310,3 +338,107 @@ fn shifts<T: Digit>() {
     n <<= 2;
     assert_eq!(n, BigInt::<T>::from(-40));
 }
+
+#[test_with(u32, u64)]
+fn gcd<T: Digit>() {
+    let n1 = BigInt::<T>::from(-84);
+    let n2 = BigInt::<T>::from(60);
+    assert_eq!(n1.gcd(&n2), BigInt::<T>::from(12));
+    assert_eq!(n1.gcd(&-&n2), BigInt::<T>::from(12));
+    assert_eq!(n1.gcd(&BigInt::<T>::default()), BigInt::<T>::from(84));
+    assert_eq!(n1.lcm(&n2), BigInt::<T>::from(420));
+}
+
+#[test_with(u32, u64)]
+fn extended_gcd<T: Digit>() {
+    for (a, b) in [(240, 46), (-240, 46), (240, -46), (-240, -46), (0, -7)] {
+        let n1 = BigInt::<T>::from(a);
+        let n2 = BigInt::<T>::from(b);
+        let (g, x, y) = n1.extended_gcd(&n2);
+        assert_eq!(g, n1.gcd(&n2));
+        assert_eq!(n1 * x + n2 * y, g);
+    }
+}
+
+#[test_with(u32, u64)]
+fn modpow<T: Digit>() {
+    let exp = BigUint::<T>::from(13u32);
+    let modulus = BigUint::<T>::from(497u32);
+    let n = BigInt::<T>::from(4);
+    assert_eq!(n.modpow(&exp, &modulus).unwrap(), BigInt::<T>::from(445));
+    let n = BigInt::<T>::from(-4);
+    assert_eq!(n.modpow(&exp, &modulus).unwrap(), BigInt::<T>::from(52));
+    let exp = BigUint::<T>::from(2u32);
+    assert_eq!(n.modpow(&exp, &modulus).unwrap(), BigInt::<T>::from(16));
+    let n = BigInt::<T>::from(-497);
+    assert_eq!(n.modpow(&exp, &modulus).unwrap(), BigInt::<T>::from(0));
+}
+
+#[test_with(u32, u64)]
+fn roots<T: Digit>() {
+    let n = BigInt::<T>::from(-1001);
+    assert_eq!(n.checked_isqrt(), None);
+    assert_eq!(n.checked_sqrt_rem(), None);
+    assert_eq!(n.checked_nth_root(3), Some(BigInt::<T>::from(-10)));
+    assert_eq!(n.checked_nth_root(2), None);
+    assert_eq!((-&n).checked_isqrt(), Some(BigInt::<T>::from(31)));
+    assert_eq!(
+        (-&n).checked_sqrt_rem(),
+        Some((BigInt::<T>::from(31), BigInt::<T>::from(40)))
+    );
+    assert!(BigInt::<T>::from(-1000).is_perfect_power());
+    assert!(!BigInt::<T>::from(-1000).is_perfect_square());
+}
+
+#[test_with(u32, u64)]
+fn ilog<T: Digit>() {
+    let n = BigInt::<T>::from(1000000);
+    assert_eq!(n.ilog10(), 6);
+    assert_eq!(n.ilog2(), 19);
+    assert_eq!(n.ilog(&BigUint::<T>::from(7u32)), 7);
+    assert_eq!((-&n).checked_ilog10(), None);
+    assert_eq!(BigInt::<T>::default().checked_ilog2(), None);
+    assert_eq!((-&n).trailing_zeros(), Some(6));
+    assert_eq!((-&n).count_ones(), 7);
+}
+
+#[test_with(u32, u64)]
+fn div_round<T: Digit>() {
+    let modes: [(RoundingMode, fn(f64) -> f64); 5] = [
+        (RoundingMode::NearestEven, f64::round_ties_even),
+        (RoundingMode::NearestAway, f64::round),
+        (RoundingMode::TowardZero, f64::trunc),
+        (RoundingMode::Floor, f64::floor),
+        (RoundingMode::Ceil, f64::ceil),
+    ];
+    for n in -20i32..=20 {
+        for d in (-6i32..=6).filter(|d| *d != 0) {
+            for (mode, round) in modes {
+                let q = BigInt::<T>::from(n)
+                    .div_round(&BigInt::from(d), mode)
+                    .unwrap();
+                let expected = round(n as f64 / d as f64) as i32;
+                assert_eq!(q, BigInt::from(expected), "{n} / {d} ({mode:?})");
+                assert!(!q.is_sign_negative() || expected < 0);
+            }
+        }
+    }
+
+    let n = (BigInt::<T>::from(5) << 200) + BigInt::from(1);
+    let d = BigInt::<T>::from(10) << 200;
+    assert_eq!(
+        n.div_round(&d, RoundingMode::NearestEven).unwrap(),
+        BigInt::from(1)
+    );
+    assert_eq!(
+        (-&n).div_round(&d, RoundingMode::TowardZero).unwrap(),
+        BigInt::from(0)
+    );
+    assert_eq!(
+        (-&n).div_round(&d, RoundingMode::Floor).unwrap(),
+        BigInt::from(-1)
+    );
+    assert!(n
+        .div_round(&BigInt::default(), RoundingMode::Floor)
+        .is_err());
+}
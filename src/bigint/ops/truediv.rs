@@ -1,14 +1,15 @@
 use crate::errors::DivisionError;
+use crate::rounding::{RoundingMode, F64};
 use crate::traits::{Digit, TrueDiv};
 use crate::BigInt;
 
 #[cfg(target_endian = "little")]
 impl<T: Digit> TrueDiv<BigInt<T>> for BigInt<T> {
     fn truediv(&self, n2: &BigInt<T>) -> Result<f64, DivisionError> {
-        if !(self.sign ^ n2.sign) {
-            Ok(self.uint.truediv(&n2.uint)?)
-        } else {
-            Ok(-self.uint.truediv(&n2.uint)?)
-        }
+        let negative = self.sign ^ n2.sign;
+        let (bits, _) =
+            self.uint
+                .truediv_bits(&n2.uint, &F64, negative, RoundingMode::NearestEven)?;
+        Ok(f64::from_bits(bits | (negative as u64) << 63))
     }
 }
@@ -1,5 +1,6 @@
-use crate::traits::Digit;
-use crate::{BigInt, BigUint};
+use crate::errors::{ParseError, TryFromIntError};
+use crate::traits::{Digit, Pow};
+use crate::{BigInt, BigUint, RoundingMode};
 
 use typed_test_gen::test_with;
 
@@ -118,6 +119,46 @@ fn hash<T: Digit>() {
     assert_eq!(map[&n2], "second");
 }
 
+#[test_with(u32, u64)]
+fn float_rounding<T: Digit>() {
+    for n in [
+        0i64,
+        1,
+        -1,
+        -(1 << 53) - 1,
+        -(1 << 53) - 3,
+        i64::MIN,
+        i64::MAX,
+    ] {
+        let a = BigInt::<T>::from(n);
+        assert_eq!(f64::from(&a), n as f64);
+        assert_eq!(f32::from(&a), n as f32);
+    }
+
+    let a = BigInt::<T>::from(-(1i64 << 54) - 6);
+    let expected = [
+        (RoundingMode::NearestEven, -18014398509481992.0),
+        (RoundingMode::NearestAway, -18014398509481992.0),
+        (RoundingMode::TowardZero, -18014398509481988.0),
+        (RoundingMode::Floor, -18014398509481992.0),
+        (RoundingMode::Ceil, -18014398509481988.0),
+    ];
+    for (mode, f) in expected {
+        assert_eq!(a.to_f64_with_rounding(mode), (f, false), "{mode:?}");
+    }
+
+    let huge = -BigInt::<T>::from(BigUint::<T>::from(1u32) << 1100);
+    assert_eq!(f64::from(&huge), f64::NEG_INFINITY);
+    assert_eq!(
+        huge.to_f64_with_rounding(RoundingMode::Floor),
+        (f64::NEG_INFINITY, false)
+    );
+    assert_eq!(
+        huge.to_f64_with_rounding(RoundingMode::Ceil),
+        (f64::MIN, false)
+    );
+}
+
 #[test_with(u32, u64)]
 fn f64<T: Digit>() {
     let mut a = BigInt::<T>::from(u64::MAX);
@@ -125,7 +166,7 @@ fn f64<T: Digit>() {
     let f: f64 = From::from(&a);
     assert_eq!(f, -1.8446744073709552e+19);
 
-    assert_eq!(format!("{:e}", a), format!("{:e}", f));
+    assert_eq!(format!("{:.16e}", a), format!("{:.16e}", f));
 }
 
 #[test_with(u32, u64)]
@@ -139,7 +180,7 @@ fn f32<T: Digit>() {
 #[test_with(u32, u64)]
 fn binary<T: Digit>() {
     let mut a = BigInt::<T>::from(BigUint::<T>::from(vec![T::MAX >> 1, T::MAX >> 1]));
-    let mut s = "0".to_string();
+    let mut s = "".to_string();
     for _ in 0..T::NB_BITS - 1 {
         s.push('1');
     }
@@ -157,7 +198,7 @@ fn binary<T: Digit>() {
 #[test_with(u32, u64)]
 fn hex<T: Digit>() {
     let mut a = BigInt::<T>::from(BigUint::<T>::from(vec![T::MAX >> 4, T::MAX >> 4]));
-    let mut s = "0".to_string();
+    let mut s = "".to_string();
     for _ in 0..(T::NB_BITS >> 2) - 1 {
         s.push('f');
     }
@@ -172,6 +213,140 @@ fn hex<T: Digit>() {
     assert_eq!(format!("{:x}", a), s2);
 }
 
+#[test_with(u32, u64)]
+fn formatter_flags<T: Digit>() {
+    for n in [
+        0i64,
+        1,
+        -1,
+        0xff,
+        -0x1234_5678_9abc_def0,
+        i64::MIN + 1,
+        i64::MAX,
+    ] {
+        let a = BigInt::<T>::from(n);
+        let abs = n.unsigned_abs();
+        let sign = if n < 0 { "-" } else { "" };
+        assert_eq!(format!("{:+}", a), format!("{:+}", n));
+        assert_eq!(format!("{:020}", a), format!("{:020}", n));
+        assert_eq!(format!("{:^+21}", a), format!("{:^+21}", n));
+        assert_eq!(format!("{:#x}", a), format!("{sign}{:#x}", abs));
+        assert_eq!(format!("{:X}", a), format!("{sign}{:X}", abs));
+        assert_eq!(format!("{:#o}", a), format!("{sign}{:#o}", abs));
+        assert_eq!(format!("{:b}", a), format!("{sign}{:b}", abs));
+    }
+    assert_eq!(format!("{:#010x}", BigInt::<T>::from(-255)), "-0x00000ff");
+}
+
+#[test_with(u32, u64)]
+fn scientific<T: Digit>() {
+    for n in [0i64, 1, -1, -15, 25, -251, 1250, -123_456_789, i64::MAX] {
+        let a = BigInt::<T>::from(n);
+        assert_eq!(format!("{:e}", a), format!("{:e}", n));
+        assert_eq!(format!("{:.1E}", a), format!("{:.1E}", n));
+        assert_eq!(format!("{:+012.2e}", a), format!("{:+012.2e}", n));
+    }
+    let a = -BigInt::<T>::from(BigUint::<T>::from(7u32).pow(500));
+    assert_eq!(format!("{:.4e}", a), "-3.5401e422");
+}
+
+#[test_with(u32, u64)]
+fn signed_bytes<T: Digit>() {
+    for n in [
+        0i64,
+        1,
+        -1,
+        127,
+        128,
+        -128,
+        -129,
+        255,
+        -256,
+        0x7fff_ffff,
+        -0x8000_0000,
+        i64::MAX,
+    ] {
+        let a = BigInt::<T>::from(n);
+        let le = n.to_le_bytes();
+        // Shortest two's complement: drop the sign extension bytes
+        let mut len = 8;
+        while len > 1
+            && (le[len - 1] as i8) >> 7 == le[len - 2] as i8 >> 7
+            && [0, 0xff].contains(&le[len - 1])
+        {
+            len -= 1;
+        }
+        assert_eq!(a.to_signed_bytes_le(), le[..len], "{n}");
+        assert_eq!(a.to_signed_bytes_be(), n.to_be_bytes()[8 - len..], "{n}");
+        assert_eq!(BigInt::<T>::from_signed_bytes_le(&le), a);
+        assert_eq!(BigInt::<T>::from_signed_bytes_le(&le[..len]), a);
+        assert_eq!(BigInt::<T>::from_signed_bytes_be(&n.to_be_bytes()), a);
+    }
+    assert_eq!(BigInt::<T>::from_signed_bytes_le(&[]), BigInt::default());
+
+    let a = -BigInt::<T>::from(BigUint::<T>::from(1u32) << 200);
+    let bytes = a.to_signed_bytes_be();
+    assert_eq!(bytes.len(), 26);
+    assert_eq!(bytes[0], 0xff);
+    assert_eq!(BigInt::<T>::from_signed_bytes_be(&bytes), a);
+}
+
+#[test_with(u32, u64)]
+fn primitives<T: Digit>() {
+    assert_eq!(
+        BigInt::<T>::from(i64::MIN).to_string(),
+        i64::MIN.to_string()
+    );
+    assert_eq!(
+        BigInt::<T>::from(i128::MIN).to_string(),
+        i128::MIN.to_string()
+    );
+    assert_eq!(BigInt::<T>::from(i8::MIN).to_string(), "-128");
+    assert_eq!(BigInt::<T>::from(-5isize), BigInt::from(-5));
+    assert_eq!(
+        BigInt::<T>::from(u128::MAX).to_string(),
+        u128::MAX.to_string()
+    );
+    assert!(!BigInt::<T>::from(0i16).is_sign_negative());
+
+    for n in [
+        0i128,
+        1,
+        -1,
+        127,
+        -128,
+        128,
+        -129,
+        i64::MIN as i128,
+        i128::MIN,
+        i128::MAX,
+    ] {
+        let a = BigInt::<T>::from(n);
+        assert_eq!(i128::try_from(&a).unwrap(), n);
+        assert_eq!(i8::try_from(&a).ok(), i8::try_from(n).ok());
+        assert_eq!(i64::try_from(&a).ok(), i64::try_from(n).ok());
+        assert_eq!(isize::try_from(&a).ok(), isize::try_from(n).ok());
+        assert_eq!(u8::try_from(&a).ok(), u8::try_from(n).ok());
+        assert_eq!(u128::try_from(&a).ok(), u128::try_from(n).ok());
+
+        assert_eq!(a.to_i8_wrapping(), n as i8);
+        assert_eq!(a.to_u16_wrapping(), n as u16);
+        assert_eq!(a.to_i64_wrapping(), n as i64);
+        assert_eq!(a.to_u64_wrapping(), n as u64);
+        assert_eq!(a.to_u128_wrapping(), n as u128);
+    }
+    assert!(matches!(
+        u32::try_from(&BigInt::<T>::from(-1)),
+        Err(TryFromIntError::Negative)
+    ));
+    let big = BigInt::<T>::from(i128::MIN) - BigInt::<T>::from(1);
+    assert!(matches!(
+        i128::try_from(&big),
+        Err(TryFromIntError::ToInt128Overflow)
+    ));
+    assert_eq!(big.to_i128_wrapping(), i128::MAX);
+}
+
 #[test_with(u32, u64)]
 fn parse<T: Digit>() {
     let n1: BigInt<T> = "-12345678901234567".parse().unwrap();
@@ -188,6 +363,79 @@ fn parse_fail<T: Digit>() {
     let _: BigInt<T> = "-123456789012-34567".parse().unwrap();
 }
 
+#[test_with(u32, u64)]
+fn str_radix<T: Digit>() {
+    let n: BigInt<T> = "-123456789012345678901234567890".parse().unwrap();
+    for radix in [2, 3, 8, 10, 16, 36] {
+        let s = n.to_str_radix(radix);
+        assert!(s.starts_with('-'));
+        assert_eq!(BigInt::<T>::from_str_radix(&s, radix).unwrap(), n);
+    }
+    assert_eq!(BigInt::<T>::default().to_str_radix(7), "0");
+
+    let e = BigInt::<T>::from_str_radix("-123456789012-34567", 10).unwrap_err();
+    assert_eq!(
+        e,
+        ParseError::UnexpectedCharacter {
+            character: '-',
+            position: 13
+        }
+    );
+    for s in ["", "-"] {
+        assert_eq!(
+            BigInt::<T>::from_str_radix(s, 16),
+            Err(ParseError::NoDigits)
+        );
+        assert_eq!(s.parse::<BigInt<T>>(), Err(ParseError::NoDigits));
+    }
+}
+
+#[test_with(u32, u64)]
+fn str_lenient<T: Digit>() {
+    let n = BigInt::<T>::from(-1_000_000);
+    for s in [
+        "-1000000",
+        "-1_000_000",
+        " -0xf_4240 ",
+        "-0o3641100",
+        "\n-0B11110100001001000000",
+    ] {
+        assert_eq!(BigInt::<T>::from_str_lenient(s).unwrap(), n);
+    }
+    assert_eq!(
+        BigInt::<T>::from_str_lenient("+42").unwrap(),
+        BigInt::from(42)
+    );
+
+    let e = BigInt::<T>::from_str_lenient(" - 1").unwrap_err();
+    assert_eq!(
+        e,
+        ParseError::UnexpectedCharacter {
+            character: ' ',
+            position: 2
+        }
+    );
+    let e = BigInt::<T>::from_str_lenient("--1").unwrap_err();
+    assert_eq!(
+        e,
+        ParseError::UnexpectedCharacter {
+            character: '-',
+            position: 1
+        }
+    );
+    let e = BigInt::<T>::from_str_lenient("-007").unwrap_err();
+    assert_eq!(
+        e,
+        ParseError::UnexpectedCharacter {
+            character: '0',
+            position: 1
+        }
+    );
+    for s in ["", "  ", "-"] {
+        assert_eq!(BigInt::<T>::from_str_lenient(s), Err(ParseError::NoDigits));
+    }
+}
+
 #[test_with(u32, u64)]
 fn from_f64<T: Digit>() {
     // Test zero
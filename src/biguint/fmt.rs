@@ -1,80 +1,135 @@
 //! (private) fmt: private module containing implementation of traits
 //! pertaining to I/O formatting.
 
+use crate::biguint::ops::to_radix;
 use crate::traits::Digit;
 use crate::BigUint;
 
-// TODO: LowerExp and UpperExp could actually be implemented for values
-// outside the range of a f64 (not sure of the actual use case).
+impl<T: Digit> BigUint<T> {
+    /// Writes the integer in the given radix, with lowercase letters for the
+    /// digits above 9.
+    ///
+    /// ```
+    /// use twibint::BigUint;
+    ///
+    /// let n = BigUint::<u64>::from(1295u32);
+    /// assert_eq!(n.to_str_radix(36), "zz");
+    /// assert_eq!(n.to_str_radix(2), "10100001111");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the radix is not in the range from 2 to 36.
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        assert!(
+            (2..=36).contains(&radix),
+            "radix must be in 2..=36, got {radix}"
+        );
+        to_radix(self, radix)
+    }
+}
+
+/// Writes `mantissa * 10^exponent` in scientific notation, rounded to the
+/// nearest (ties to even) at the precision of the formatter, or exactly if
+/// there is none.
+pub(crate) fn exp<T: Digit>(
+    f: &mut std::fmt::Formatter<'_>,
+    is_nonnegative: bool,
+    mantissa: &BigUint<T>,
+    exponent: isize,
+    upper: bool,
+) -> std::fmt::Result {
+    let mut digits = to_radix(mantissa, 10).into_bytes();
+    let mut exponent = match mantissa.is_zero() {
+        true => 0,
+        false => exponent + digits.len() as isize - 1,
+    };
+
+    match f.precision() {
+        Some(precision) if digits.len() > precision + 1 => {
+            let (kept, dropped) = digits.split_at(precision + 1);
+            let round_up = match dropped[0] {
+                b'6'..=b'9' => true,
+                b'5' => dropped[1..].iter().any(|d| *d != b'0') || kept[precision] % 2 == 1,
+                _ => false,
+            };
+            digits.truncate(precision + 1);
+            if round_up {
+                match digits.iter().rposition(|d| *d != b'9') {
+                    Some(i) => {
+                        digits[i] += 1;
+                        digits[i + 1..].fill(b'0');
+                    }
+                    None => {
+                        digits.fill(b'0');
+                        digits[0] = b'1';
+                        exponent += 1;
+                    }
+                }
+            }
+        }
+        Some(precision) => digits.resize(precision + 1, b'0'),
+        None => {
+            let nb_digits = digits.iter().rposition(|d| *d != b'0').unwrap_or(0) + 1;
+            digits.truncate(nb_digits);
+        }
+    }
+
+    let mut buf = String::with_capacity(digits.len() + 24);
+    buf.push(digits[0] as char);
+    if digits.len() > 1 {
+        buf.push('.');
+        buf.extend(digits[1..].iter().map(|d| *d as char));
+    }
+    buf.push(if upper { 'E' } else { 'e' });
+    buf.push_str(&exponent.to_string());
+    f.pad_integral(is_nonnegative, "", &buf)
+}
+
 impl<T: Digit> std::fmt::LowerExp for BigUint<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let val = f64::from(self);
-        std::fmt::LowerExp::fmt(&val, f)
+        exp(f, true, self, 0, false)
     }
 }
 impl<T: Digit> std::fmt::UpperExp for BigUint<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let val = f64::from(self);
-        std::fmt::UpperExp::fmt(&val, f)
+        exp(f, true, self, 0, true)
     }
 }
 
-impl<T: Digit> std::fmt::LowerHex for BigUint<T> {
+impl<T: Digit> std::fmt::Display for BigUint<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut ret = "".to_string();
-        for c in self.val.iter().rev() {
-            let binary = &format!("{:x}", c);
-            let mut full_binary = "".to_string();
-            for _ in 0..(T::NB_BITS / 4) - binary.len() {
-                full_binary.push('0');
-            }
-            full_binary.push_str(&binary);
-            ret.push_str(&full_binary);
-        }
+        f.pad_integral(true, "", &to_radix(self, 10))
+    }
+}
 
-        write!(f, "{}", ret)
+impl<T: Digit> std::fmt::LowerHex for BigUint<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad_integral(true, "0x", &to_radix(self, 16))
     }
 }
 impl<T: Digit> std::fmt::UpperHex for BigUint<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut ret = "".to_string();
-        for c in self.val.iter().rev() {
-            let binary = &format!("{:X}", c);
-            let mut full_binary = "".to_string();
-            for _ in 0..(T::NB_BITS / 4) - binary.len() {
-                full_binary.push('0');
-            }
-            full_binary.push_str(&binary);
-            ret.push_str(&full_binary);
-        }
-
-        write!(f, "{}", ret)
+        f.pad_integral(true, "0x", &to_radix(self, 16).to_uppercase())
     }
 }
 
-impl<T: Digit> std::fmt::Display for BigUint<T> {
+impl<T: Digit> std::fmt::Octal for BigUint<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", String::from(self))
+        f.pad_integral(true, "0o", &to_radix(self, 8))
     }
 }
 
-pub(crate) fn bin<T: Digit>(val: &[T], f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-    let mut ret = "".to_string();
-    for c in val.iter().rev() {
-        let binary = &format!("{:b}", c);
-        let mut full_binary = "".to_string();
-        for _ in 0..T::NB_BITS - binary.len() {
-            full_binary.push('0');
-        }
-        full_binary.push_str(&binary);
-        ret.push_str(&full_binary);
+impl<T: Digit> std::fmt::Binary for BigUint<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad_integral(true, "0b", &to_radix(self, 2))
     }
-
-    write!(f, "{}", ret)
 }
 
-impl<T: Digit> std::fmt::Binary for BigUint<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        bin(&self.val, f)
+/// Writes all the bits of the digits, leading zeros included
+pub(crate) fn bin<T: Digit>(val: &[T], f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    for c in val.iter().rev() {
+        write!(f, "{:0width$b}", c, width = T::NB_BITS)?;
     }
+    Ok(())
 }
@@ -5,106 +5,200 @@
 
 use core::cmp::Ordering;
 
-use crate::biguint::Digits;
+use crate::biguint::ops::from_radix;
+use crate::biguint::ops::to_radix;
 use crate::errors::FromFloatError;
-use crate::errors::UnexpectedCharacterError;
+use crate::errors::ParseError;
+use crate::errors::TryFromIntError;
+use crate::rounding::{RoundingMode, F32, F64};
 use crate::traits::Digit;
 use crate::BigUint;
 
 impl<T: Digit> From<&BigUint<T>> for f64 {
     fn from(int: &BigUint<T>) -> f64 {
-        let mut exponent = 0i64;
-
-        // Get the correct 52 bits of mantissa
-        let extra_bits = int.nb_bits() - 53;
-        let cleaned = (int >> extra_bits) + T::from(int.bit(extra_bits - 1));
-
-        exponent -= extra_bits as i64;
-
-        // Handle overflow or underflow (probably not correct, some answers get mapped to NaNs)
-        if exponent > 52 + 1023 {
-            return 0f64;
-        } else if exponent <= -4096i64 + 52 + 1023 {
-            return f64::INFINITY;
-        }
-
-        // Get actual mantissa and exponent biased 1023
-        let mut mantissa: u64 = cleaned.into();
-        mantissa ^= 1u64 << 52;
-        let exponent_u64: u64 = (52 + 1023 - exponent).try_into().unwrap();
-        f64::from_bits((exponent_u64 << 52) | mantissa)
+        int.to_f64_with_rounding(RoundingMode::NearestEven).0
     }
 }
 
 impl<T: Digit> From<&BigUint<T>> for f32 {
     fn from(int: &BigUint<T>) -> f32 {
-        let mut exponent = 0i32;
-
-        // Get the correct 23 bits of mantissa
-        let extra_bits = int.nb_bits() - 24;
-        let cleaned = (int >> extra_bits) + T::from(int.bit(extra_bits - 1));
+        let (bits, _) = F32.round(int, 0, false, false, RoundingMode::NearestEven);
+        f32::from_bits(bits as u32)
+    }
+}
 
-        exponent -= extra_bits as i32;
+impl<T: Digit> BigUint<T> {
+    /// Converts to the `f64` obtained by rounding with the given mode, and
+    /// tells whether the conversion is exact. Integers too large for a `f64`
+    /// give infinity, or `f64::MAX` when rounding toward zero or down.
+    ///
+    /// ```
+    /// use twibint::{BigUint, RoundingMode};
+    ///
+    /// let n = BigUint::<u64>::from((1u64 << 53) + 1);
+    /// assert_eq!(n.to_f64_with_rounding(RoundingMode::NearestEven), (9007199254740992.0, false));
+    /// assert_eq!(n.to_f64_with_rounding(RoundingMode::Ceil), (9007199254740994.0, false));
+    /// assert_eq!(BigUint::<u64>::from(3u32).to_f64_with_rounding(RoundingMode::Floor), (3.0, true));
+    /// ```
+    pub fn to_f64_with_rounding(&self, mode: RoundingMode) -> (f64, bool) {
+        let (bits, exact) = F64.round(self, 0, false, false, mode);
+        (f64::from_bits(bits), exact)
+    }
+}
 
-        // Handle overflow or underflow (probably not correct, some answers get mapped to NaNs)
-        if exponent > 23 + 127 {
-            return 0f32;
-        } else if exponent <= -512 + 23 + 127 {
-            return f32::INFINITY;
+impl<T: Digit> BigUint<T> {
+    /// Reads an integer written in the given radix, with letters of any case
+    /// for the digits above 9. An empty string is an error.
+    ///
+    /// ```
+    /// use twibint::{BigUint, ParseError};
+    ///
+    /// let n = BigUint::<u64>::from_str_radix("fF", 16).unwrap();
+    /// assert_eq!(n, BigUint::from(255u32));
+    ///
+    /// let e = BigUint::<u64>::from_str_radix("10201", 2).unwrap_err();
+    /// assert_eq!(e, ParseError::UnexpectedCharacter { character: '2', position: 2 });
+    /// assert_eq!(BigUint::<u64>::from_str_radix("", 10), Err(ParseError::NoDigits));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the radix is not in the range from 2 to 36.
+    pub fn from_str_radix(s: &str, radix: u32) -> Result<BigUint<T>, ParseError> {
+        assert!(
+            (2..=36).contains(&radix),
+            "radix must be in 2..=36, got {radix}"
+        );
+        let values = s
+            .char_indices()
+            .map(|(position, character)| match character.to_digit(radix) {
+                Some(v) => Ok(v as u8),
+                None => Err(ParseError::UnexpectedCharacter {
+                    character,
+                    position,
+                }),
+            })
+            .collect::<Result<Vec<u8>, _>>()?;
+        if values.is_empty() {
+            return Err(ParseError::NoDigits);
         }
-
-        // Get actual mantissa and exponent biased 127
-        let mut mantissa: u32 = cleaned.into();
-        mantissa ^= 1u32 << 23;
-        let exponent_u32: u32 = (23 + 127 - exponent).try_into().unwrap();
-        f32::from_bits((exponent_u32 << 23) | mantissa)
+        Ok(from_radix(&values, radix))
     }
 }
 
-impl<T: Digit> std::str::FromStr for BigUint<T> {
-    type Err = UnexpectedCharacterError;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut ret = BigUint::<T>::new(T::ZERO);
-
-        let mut base = BigUint::<T>::new(T::ONE);
-        for c in s.chars().rev() {
-            let v: BigUint<T> = match c.to_digit(10) {
-                Some(val) => val,
-                None => return Err(UnexpectedCharacterError(c)),
+/// Reads an integer literal, with surrounding whitespace, an optional sign, an
+/// optional `0x`, `0o` or `0b` prefix, and single underscores between digits
+/// (or right after the prefix). Like in Python, a nonzero decimal integer
+/// cannot start with a zero. Returns whether the integer is positive, and its
+/// absolute value.
+pub(crate) fn parse_literal<T: Digit>(s: &str) -> Result<(bool, BigUint<T>), ParseError> {
+    let start = s.len() - s.trim_start().len();
+    let body = s.trim();
+    let (sign, rest) = match body.strip_prefix('-') {
+        Some(rest) => (false, rest),
+        None => (true, body.strip_prefix('+').unwrap_or(body)),
+    };
+    let (radix, digits) = match rest.get(..2) {
+        Some("0x" | "0X") => (16, &rest[2..]),
+        Some("0o" | "0O") => (8, &rest[2..]),
+        Some("0b" | "0B") => (2, &rest[2..]),
+        _ => (10, rest),
+    };
+    let offset = start + body.len() - digits.len();
+
+    let mut values = Vec::with_capacity(digits.len());
+    let mut underscore_allowed = digits.len() < rest.len();
+    let mut trailing_underscore = None;
+    for (i, character) in digits.char_indices() {
+        let error = ParseError::UnexpectedCharacter {
+            character,
+            position: offset + i,
+        };
+        match character.to_digit(radix) {
+            Some(v) => {
+                values.push(v as u8);
+                underscore_allowed = true;
+                trailing_underscore = None;
             }
-            .into();
-
-            ret += &base * &v;
-            base *= BigUint::<T>::from(10u32);
+            None if character == '_' && underscore_allowed => {
+                underscore_allowed = false;
+                trailing_underscore = Some(error);
+            }
+            None => return Err(error),
         }
+    }
+    if let Some(error) = trailing_underscore {
+        return Err(error);
+    }
 
-        ret.remove_leading_zeros();
-        return Ok(ret);
+    // A prefix has to be followed by digits, and there is no integer at all
+    // without a prefix
+    if values.is_empty() {
+        if digits.len() == rest.len() {
+            return Err(ParseError::NoDigits);
+        }
+        let position = offset - 1;
+        return Err(ParseError::UnexpectedCharacter {
+            character: s[position..].chars().next().unwrap(),
+            position,
+        });
+    }
+    if radix == 10 && values[0] == 0 && values.iter().any(|v| *v != 0) {
+        return Err(ParseError::UnexpectedCharacter {
+            character: '0',
+            position: offset,
+        });
     }
+    Ok((sign, from_radix(&values, radix)))
 }
 
-impl<T: Digit> From<&BigUint<T>> for Digits {
-    fn from(b: &BigUint<T>) -> Digits {
-        let mut digits = Digits::new(0);
-
-        for bit in b.bits().rev() {
-            digits.times_2();
-            digits.add_n_at_k(bit as u8, 0);
+impl<T: Digit> BigUint<T> {
+    /// Reads an integer with the rules of Rust and Python literals: surrounding
+    /// whitespace is ignored, a leading `+` is allowed, `0x`, `0o` and `0b`
+    /// prefixes select the radix, and digits can be grouped with underscores.
+    /// As in Python, a string without digits is an error, and so is a nonzero
+    /// decimal with leading zeros.
+    ///
+    /// ```
+    /// use twibint::{BigUint, ParseError};
+    ///
+    /// let n = BigUint::<u64>::from_str_lenient(" +0xdead_BEEF\n").unwrap();
+    /// assert_eq!(n, BigUint::from(0xdeadbeefu32));
+    /// assert_eq!(BigUint::<u64>::from_str_lenient("1_000").unwrap(), BigUint::from(1000u32));
+    ///
+    /// let e = BigUint::<u64>::from_str_lenient("1__000").unwrap_err();
+    /// assert_eq!(e, ParseError::UnexpectedCharacter { character: '_', position: 2 });
+    /// ```
+    pub fn from_str_lenient(s: &str) -> Result<BigUint<T>, ParseError> {
+        match parse_literal(s)? {
+            (true, uint) => Ok(uint),
+            (false, _) => {
+                let position = s.find('-').unwrap();
+                Err(ParseError::UnexpectedCharacter {
+                    character: '-',
+                    position,
+                })
+            }
         }
+    }
+}
 
-        digits
+impl<T: Digit> std::str::FromStr for BigUint<T> {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        BigUint::from_str_radix(s, 10)
     }
 }
 
 impl<T: Digit> From<&BigUint<T>> for String {
     fn from(b: &BigUint<T>) -> String {
-        String::from(&Digits::from(b))
+        to_radix(b, 10)
     }
 }
 
 impl<T: Digit> From<BigUint<T>> for String {
     fn from(b: BigUint<T>) -> String {
-        String::from(&Digits::from(&b))
+        to_radix(&b, 10)
     }
 }
 
@@ -136,56 +230,47 @@ impl<T: Digit> From<(T, T)> for BigUint<T> {
     }
 }
 
-#[derive(Debug)]
-pub enum IntoUintError {
-    ToUint16Overflow,
-    ToUint32Overflow,
-    ToUint64Overflow,
-    ToDigitOverflow,
-    ToDoubleDigitOverflow,
-}
-
 impl<T: Digit> BigUint<T> {
-    pub fn try_into_double_digit(&self) -> Result<T::Double, IntoUintError> {
+    pub fn try_into_double_digit(&self) -> Result<T::Double, TryFromIntError> {
         match self.val.len() {
             0 => unreachable!(),
             1 => Ok(self.val[0].to_double()),
             2 => Ok(self.val[0].to_double() + (self.val[1].to_double() << T::NB_BITS)),
-            _ => Err(IntoUintError::ToDoubleDigitOverflow),
+            _ => Err(TryFromIntError::ToDoubleDigitOverflow),
         }
     }
 
-    pub fn try_into_digit(&self) -> Result<T, IntoUintError> {
+    pub fn try_into_digit(&self) -> Result<T, TryFromIntError> {
         match self.val.len() {
             0 => unreachable!(),
             1 => Ok(self.val[0]),
-            _ => Err(IntoUintError::ToDigitOverflow),
+            _ => Err(TryFromIntError::ToDigitOverflow),
         }
     }
 }
 
 impl<T: Digit> TryFrom<&BigUint<T>> for u16 {
-    type Error = IntoUintError;
+    type Error = TryFromIntError;
     fn try_from(uint: &BigUint<T>) -> Result<u16, Self::Error> {
         let val_64: u64 = uint.try_into()?;
         match val_64.try_into() {
-            Err(_) => Err(IntoUintError::ToUint16Overflow),
+            Err(_) => Err(TryFromIntError::ToUint16Overflow),
             Ok(val) => Ok(val),
         }
     }
 }
 impl<T: Digit> TryFrom<&BigUint<T>> for u32 {
-    type Error = IntoUintError;
+    type Error = TryFromIntError;
     fn try_from(uint: &BigUint<T>) -> Result<u32, Self::Error> {
         let val_64: u64 = uint.try_into()?;
         match val_64.try_into() {
-            Err(_) => Err(IntoUintError::ToUint32Overflow),
+            Err(_) => Err(TryFromIntError::ToUint32Overflow),
             Ok(val) => Ok(val),
         }
     }
 }
 impl<T: Digit> TryFrom<&BigUint<T>> for u64 {
-    type Error = IntoUintError;
+    type Error = TryFromIntError;
     fn try_from(uint: &BigUint<T>) -> Result<u64, Self::Error> {
         match T::NB_BITS.cmp(&64) {
             Ordering::Greater => unreachable!(),
@@ -197,7 +282,7 @@ impl<T: Digit> TryFrom<&BigUint<T>> for u64 {
                 let mut remaining = 64;
                 while remaining >= T::NB_BITS && idx < uint.val.len() {
                     let val: u64 = match uint.val[idx].try_into() {
-                        Err(_) => return Err(IntoUintError::ToUint64Overflow),
+                        Err(_) => return Err(TryFromIntError::ToUint64Overflow),
                         Ok(val) => val,
                     };
                     ret |= val << (idx * T::NB_BITS);
@@ -205,7 +290,7 @@ impl<T: Digit> TryFrom<&BigUint<T>> for u64 {
                     remaining -= T::NB_BITS;
                 }
                 if idx < uint.val.len() {
-                    return Err(IntoUintError::ToUint64Overflow);
+                    return Err(TryFromIntError::ToUint64Overflow);
                 }
                 Ok(ret)
             }
@@ -213,6 +298,41 @@ impl<T: Digit> TryFrom<&BigUint<T>> for u64 {
     }
 }
 
+impl<T: Digit> TryFrom<&BigUint<T>> for u128 {
+    type Error = TryFromIntError;
+    fn try_from(uint: &BigUint<T>) -> Result<u128, Self::Error> {
+        match uint.nb_bits() {
+            0..=128 => Ok(uint.low_u128()),
+            _ => Err(TryFromIntError::ToUint128Overflow),
+        }
+    }
+}
+
+/// Conversions to the other primitives go through u128
+macro_rules! try_from_biguint {
+    ($($t:ty => $error:ident),*) => {$(
+        impl<T: Digit> TryFrom<&BigUint<T>> for $t {
+            type Error = TryFromIntError;
+            fn try_from(uint: &BigUint<T>) -> Result<$t, Self::Error> {
+                u128::try_from(uint)
+                    .ok()
+                    .and_then(|val| val.try_into().ok())
+                    .ok_or(TryFromIntError::$error)
+            }
+        }
+    )*};
+}
+try_from_biguint!(
+    u8 => ToUint8Overflow,
+    usize => ToUsizeOverflow,
+    i8 => ToInt8Overflow,
+    i16 => ToInt16Overflow,
+    i32 => ToInt32Overflow,
+    i64 => ToInt64Overflow,
+    i128 => ToInt128Overflow,
+    isize => ToIsizeOverflow
+);
+
 impl<T: Digit> From<u32> for BigUint<T> {
     fn from(n: u32) -> BigUint<T> {
         BigUint::<T>::from(T::decomposition_from_u32(n))
@@ -225,22 +345,77 @@ impl<T: Digit> From<u64> for BigUint<T> {
     }
 }
 
-impl<T: Digit> From<BigUint<T>> for u16 {
-    fn from(uint: BigUint<T>) -> u16 {
-        <u16 as TryFrom<&BigUint<T>>>::try_from(&uint).unwrap()
+impl<T: Digit> From<u8> for BigUint<T> {
+    fn from(n: u8) -> BigUint<T> {
+        BigUint::<T>::from(n as u32)
+    }
+}
+
+impl<T: Digit> From<u16> for BigUint<T> {
+    fn from(n: u16) -> BigUint<T> {
+        BigUint::<T>::from(n as u32)
+    }
+}
+
+impl<T: Digit> From<usize> for BigUint<T> {
+    fn from(n: usize) -> BigUint<T> {
+        BigUint::<T>::from(n as u64)
     }
 }
-impl<T: Digit> From<BigUint<T>> for u32 {
-    fn from(uint: BigUint<T>) -> u32 {
-        <u32 as TryFrom<&BigUint<T>>>::try_from(&uint).unwrap()
+
+impl<T: Digit> From<u128> for BigUint<T> {
+    fn from(n: u128) -> BigUint<T> {
+        let val: Vec<T> = (0..128 / T::NB_BITS)
+            .map(|i| T::decomposition_from_u64((n >> (i * T::NB_BITS)) as u64)[0])
+            .collect();
+        BigUint::<T>::from(val)
     }
 }
-impl<T: Digit> From<BigUint<T>> for u64 {
-    fn from(uint: BigUint<T>) -> u64 {
-        <u64 as TryFrom<&BigUint<T>>>::try_from(&uint).unwrap()
+
+impl<T: Digit> BigUint<T> {
+    /// The lowest 128 bits of the integer
+    pub(crate) fn low_u128(&self) -> u128 {
+        self.val
+            .iter()
+            .take(128 / T::NB_BITS)
+            .rev()
+            .fold(0u128, |acc, d| {
+                let d: u64 = (*d).try_into().ok().unwrap();
+                (acc << T::NB_BITS) | d as u128
+            })
     }
 }
 
+/// Explicitly truncating conversions to the primitives
+macro_rules! to_wrapping {
+    ($($name:ident -> $t:ty),*) => {
+        impl<T: Digit> BigUint<T> {$(
+            #[doc = concat!(
+                "Converts to a `", stringify!($t), "`, keeping only the lowest bits ",
+                "of the integer, like an `as` cast between primitive integers."
+            )]
+            #[inline]
+            pub fn $name(&self) -> $t {
+                self.low_u128() as $t
+            }
+        )*}
+    };
+}
+to_wrapping!(
+    to_u8_wrapping -> u8,
+    to_u16_wrapping -> u16,
+    to_u32_wrapping -> u32,
+    to_u64_wrapping -> u64,
+    to_u128_wrapping -> u128,
+    to_usize_wrapping -> usize,
+    to_i8_wrapping -> i8,
+    to_i16_wrapping -> i16,
+    to_i32_wrapping -> i32,
+    to_i64_wrapping -> i64,
+    to_i128_wrapping -> i128,
+    to_isize_wrapping -> isize
+);
+
 #[cfg(target_endian = "little")]
 impl<T: Digit> TryFrom<f64> for BigUint<T> {
     type Error = FromFloatError<f64>;
@@ -296,3 +471,114 @@ impl<T: Digit> TryFrom<f32> for BigUint<T> {
         })
     }
 }
+
+impl<T: Digit> BigUint<T> {
+    /// Returns the bytes of the integer, least significant first, without
+    /// leading zeros (zero is a single zero byte).
+    ///
+    /// ```
+    /// use twibint::BigUint;
+    ///
+    /// let n = BigUint::<u64>::from(0x1234567u32);
+    /// assert_eq!(n.to_bytes_le(), vec![0x67, 0x45, 0x23, 0x01]);
+    /// ```
+    pub fn to_bytes_le(&self) -> Vec<u8> {
+        let bytes_per_digit = T::NB_BITS / 8;
+        let mut ret = vec![0u8; self.val.len() * bytes_per_digit];
+        for (d, buff) in self.val.iter().zip(ret.chunks_mut(bytes_per_digit)) {
+            d.write_bytes(buff);
+        }
+        let len = ret.iter().rposition(|b| *b != 0).unwrap_or(0) + 1;
+        ret.truncate(len);
+        ret
+    }
+
+    /// Returns the bytes of the integer, most significant first, without
+    /// leading zeros (zero is a single zero byte).
+    ///
+    /// ```
+    /// use twibint::BigUint;
+    ///
+    /// let n = BigUint::<u64>::from(0x1234567u32);
+    /// assert_eq!(n.to_bytes_be(), vec![0x01, 0x23, 0x45, 0x67]);
+    /// ```
+    pub fn to_bytes_be(&self) -> Vec<u8> {
+        let mut ret = self.to_bytes_le();
+        ret.reverse();
+        ret
+    }
+
+    /// Builds an integer from its bytes, least significant first. An empty
+    /// slice gives zero.
+    ///
+    /// ```
+    /// use twibint::BigUint;
+    ///
+    /// let n = BigUint::<u64>::from_bytes_le(&[0x67, 0x45, 0x23, 0x01, 0x00]);
+    /// assert_eq!(n, BigUint::from(0x1234567u32));
+    /// ```
+    pub fn from_bytes_le(bytes: &[u8]) -> BigUint<T> {
+        let bytes_per_digit = T::NB_BITS / 8;
+        let mut val: Vec<T> = bytes
+            .chunks(bytes_per_digit)
+            .map(|chunk| {
+                let mut buff = [0u8; 8];
+                buff[..chunk.len()].copy_from_slice(chunk);
+                T::read_bytes(&buff[..bytes_per_digit])
+            })
+            .collect();
+        if val.is_empty() {
+            val.push(T::ZERO);
+        }
+        let mut ret = BigUint::from(val);
+        ret.remove_leading_zeros();
+        ret
+    }
+
+    /// Builds an integer from its bytes, most significant first. An empty
+    /// slice gives zero.
+    ///
+    /// ```
+    /// use twibint::BigUint;
+    ///
+    /// let n = BigUint::<u64>::from_bytes_be(&[0x00, 0x01, 0x23, 0x45, 0x67]);
+    /// assert_eq!(n, BigUint::from(0x1234567u32));
+    /// ```
+    pub fn from_bytes_be(bytes: &[u8]) -> BigUint<T> {
+        let mut bytes = bytes.to_vec();
+        bytes.reverse();
+        BigUint::from_bytes_le(&bytes)
+    }
+
+    /// Writes the bytes of the integer, most significant first, at the end of
+    /// `buff`, and fills the start of `buff` with zeros, as fixed-width
+    /// encodings expect.
+    ///
+    /// ```
+    /// use twibint::BigUint;
+    ///
+    /// let mut buff = [0xffu8; 6];
+    /// BigUint::<u64>::from(0x1234567u32).write_bytes_be_padded(&mut buff);
+    /// assert_eq!(buff, [0x00, 0x00, 0x01, 0x23, 0x45, 0x67]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the integer does not fit in `buff`.
+    pub fn write_bytes_be_padded(&self, buff: &mut [u8]) {
+        let bytes = self.to_bytes_be();
+        let bytes = match self.is_zero() {
+            true => &[][..],
+            false => &bytes[..],
+        };
+        assert!(
+            bytes.len() <= buff.len(),
+            "integer of {} bytes does not fit in a buffer of {} bytes",
+            bytes.len(),
+            buff.len()
+        );
+        let (padding, end) = buff.split_at_mut(buff.len() - bytes.len());
+        padding.fill(0);
+        end.copy_from_slice(bytes);
+    }
+}
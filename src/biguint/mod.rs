@@ -8,14 +8,9 @@ pub(crate) mod fmt;
 pub(crate) mod froms;
 pub(crate) mod ops;
 
-mod digits_vec;
-use digits_vec::Digits;
-
 #[cfg(test)]
 mod test;
 
-// TODO: implement ilog2 and that sort of things
-
 /// Representation of an unsigned integer with an infinite number of bits (above
 /// a certain position, they are all 0).
 ///
@@ -118,6 +113,31 @@ impl<T: Digit> BigUint<T> {
         (0..self.nb_bits()).map(|b| self.bit(b))
     }
 
+    /// Returns the number of zeros below the lowest one in the binary
+    /// representation, or None for zero.
+    ///
+    /// ```
+    /// use twibint::BigUint;
+    /// assert_eq!(BigUint::<u64>::from(40u64).trailing_zeros(), Some(3));
+    /// assert_eq!(BigUint::<u64>::default().trailing_zeros(), None);
+    /// ```
+    #[inline]
+    pub fn trailing_zeros(&self) -> Option<usize> {
+        let idx = self.val.iter().position(|d| *d != T::ZERO)?;
+        Some(idx * T::NB_BITS + self.val[idx].trailing_zeros() as usize)
+    }
+
+    /// Returns the number of ones in the binary representation.
+    ///
+    /// ```
+    /// use twibint::BigUint;
+    /// assert_eq!(BigUint::<u64>::from(u64::MAX).count_ones(), 64);
+    /// ```
+    #[inline]
+    pub fn count_ones(&self) -> usize {
+        self.val.iter().map(|d| d.count_ones() as usize).sum()
+    }
+
     /// Copies data from other into self, keeping self's allocation if possible
     pub fn copy_from(&mut self, other: &Self) {
         self.val.resize(other.val.len(), T::ZERO);
@@ -136,6 +156,11 @@ impl<T: Digit> BigUint<T> {
     pub(crate) fn ord(&self, other: &[T]) -> Ordering {
         ord(&self.val, other)
     }
+
+    #[inline]
+    pub(crate) fn is_zero(&self) -> bool {
+        self.val.iter().all(|d| *d == T::ZERO)
+    }
 }
 
 #[inline]
@@ -174,9 +174,10 @@ fn div_3n_by_2n<T: Digit>(n: &[T], d: &[T], q: &mut [T], r: &mut BigInt<T>, buff
             r.uint.val.resize(3 * size / 2, T::ZERO);
             r.uint.val[..size / 2].fill(T::ZERO);
             r.uint.val[size / 2..].copy_from_slice(&n[size / 2..]);
-            add_assign(&mut r.uint.val[size / 2..], &d[size / 2..]);
+            // An overflow of the addition is compensated by the subtraction
+            let carry = add_assign(&mut r.uint.val[size / 2..], &d[size / 2..]);
             let remaining = sub_assign(&mut r.uint.val[size..], &d[size / 2..]);
-            debug_assert!(!remaining);
+            debug_assert_eq!(carry, remaining);
         }
     };
 
@@ -239,6 +240,11 @@ pub(crate) fn rem_div<T: Digit>(
         return super::newton_raphson::rem_div(n, d);
     }
 
+    // The block loop below assumes at least one division to perform
+    if n < d {
+        return Ok((BigUint::default(), n.clone()));
+    }
+
     // Compute the target size of d, which the size of "meta blocks", acting as single
     // digits in a very high level schoolbook division
     let nb_blocks = (d.val.len() / RECURSION_THRESHOLD).next_power_of_two();
@@ -0,0 +1,173 @@
+//! half_gcd: subquadratic reduction of a pair of integers to about half their
+//! size, following Möller's formulation of Schönhage's algorithm.
+
+use super::lehmer::lehmer_matrix;
+use super::small_quotient_rem_div;
+use super::HALF_GCD_THRESHOLD;
+use crate::traits::Digit;
+use crate::BigInt;
+use crate::BigUint;
+
+/// Unimodular matrix `[[m00, m01], [m10, m11]]` with non-negative entries,
+/// such that `(a, b) = M (a', b')` where `(a', b')` is the reduced pair.
+#[derive(Clone, Debug)]
+pub(super) struct Matrix<T: Digit> {
+    pub(super) m: [BigUint<T>; 4],
+}
+
+impl<T: Digit> Matrix<T> {
+    fn identity() -> Self {
+        Self {
+            m: [
+                BigUint::new(T::ONE),
+                BigUint::default(),
+                BigUint::default(),
+                BigUint::new(T::ONE),
+            ],
+        }
+    }
+
+    pub(super) fn is_identity(&self) -> bool {
+        self.m[1].is_zero() && self.m[2].is_zero()
+    }
+
+    /// Returns the entries of the inverse matrix, in the same order
+    pub(super) fn inverse_entries(&self) -> [BigInt<T>; 4] {
+        let [m00, m01, m10, m11] = &self.m;
+        [
+            BigInt::from(m11.clone()),
+            -BigInt::from(m01.clone()),
+            -BigInt::from(m10.clone()),
+            BigInt::from(m00.clone()),
+        ]
+    }
+
+    /// Returns the reduced pair `M^-1 (a, b)`, or None if it has a negative entry
+    fn reduce(&self, a: &BigUint<T>, b: &BigUint<T>) -> Option<(BigUint<T>, BigUint<T>)> {
+        let [m00, m01, m10, m11] = &self.m;
+        let (p1, n1) = (m11 * a, m01 * b);
+        let (p2, n2) = (m00 * b, m10 * a);
+        if p1 < n1 || p2 < n2 {
+            return None;
+        }
+        Some((p1 - n1, p2 - n2))
+    }
+
+    fn mul(&self, other: &Matrix<T>) -> Matrix<T> {
+        let [a00, a01, a10, a11] = &self.m;
+        let [b00, b01, b10, b11] = &other.m;
+        Matrix {
+            m: [
+                a00 * b00 + a01 * b10,
+                a00 * b01 + a01 * b11,
+                a10 * b00 + a11 * b10,
+                a10 * b01 + a11 * b11,
+            ],
+        }
+    }
+}
+
+/// A Euclidean step may be performed on `(a, b)` while keeping both operands
+/// above `2^s`
+fn can_step<T: Digit>(a: &BigUint<T>, b: &BigUint<T>, s: usize) -> bool {
+    let (big, small) = if a > b { (a, b) } else { (b, a) };
+    small.nb_bits() > s + 1 && (big - small).nb_bits() > s + 1
+}
+
+/// Performs a single Euclidean step, keeping the reduced operand above `2^s`
+fn step<T: Digit>(m: &mut Matrix<T>, a: &mut BigUint<T>, b: &mut BigUint<T>, s: usize) {
+    let a_is_bigger = *a > *b;
+    let (big, small) = if a_is_bigger { (a, b) } else { (b, a) };
+
+    let (mut q, mut r) = small_quotient_rem_div(big, small);
+    if r.nb_bits() <= s + 1 {
+        q -= T::ONE;
+        r += &*small;
+    }
+    *big = r;
+
+    let [m00, m01, m10, m11] = &mut m.m;
+    if a_is_bigger {
+        *m01 += &q * &*m00;
+        *m11 += &q * &*m10;
+    } else {
+        *m00 += &q * &*m01;
+        *m10 += &q * &*m11;
+    }
+}
+
+/// Reduces `(a, b)` until no step can be performed anymore or the biggest of
+/// them has at most `target_bits` bits. Lehmer steps are used when possible.
+fn reduce_until<T: Digit>(
+    m: &mut Matrix<T>,
+    a: &mut BigUint<T>,
+    b: &mut BigUint<T>,
+    s: usize,
+    target_bits: usize,
+) {
+    while a.nb_bits().max(b.nb_bits()) > target_bits && can_step(a, b, s) {
+        let lehmer = if *a >= *b {
+            lehmer_matrix(a, b)
+        } else {
+            lehmer_matrix(b, a).map(|l| l.swapped())
+        };
+
+        if let Some(lehmer) = lehmer {
+            let (new_a, new_b) = lehmer.reduce(a, b);
+            if new_a.nb_bits() > s + 1 && new_b.nb_bits() > s + 1 {
+                *m = lehmer.left_mul(m);
+                (*a, *b) = (new_a, new_b);
+                continue;
+            }
+        }
+
+        step(m, a, b, s);
+    }
+}
+
+/// Returns `(M, a', b')` such that `(a, b) = M (a', b')`, with `a'` and `b'`
+/// of about half the size of the biggest of `a` and `b`
+pub(super) fn half_gcd<T: Digit>(
+    a: &BigUint<T>,
+    b: &BigUint<T>,
+) -> (Matrix<T>, BigUint<T>, BigUint<T>) {
+    let n = a.nb_bits().max(b.nb_bits());
+    let s = n / 2 + 1;
+    let mut m = Matrix::identity();
+    let (mut a, mut b) = (a.clone(), b.clone());
+
+    if !can_step(&a, &b, s) {
+        return (m, a, b);
+    }
+
+    if n < HALF_GCD_THRESHOLD * T::NB_BITS {
+        reduce_until(&mut m, &mut a, &mut b, s, 0);
+        return (m, a, b);
+    }
+
+    // First recursion, on the leading half of the operands
+    let p = n / 2;
+    let (m1, _, _) = half_gcd(&(&a >> p), &(&b >> p));
+    if !m1.is_identity() {
+        if let Some((new_a, new_b)) = m1.reduce(&a, &b) {
+            (a, b, m) = (new_a, new_b, m1);
+        }
+    }
+
+    reduce_until(&mut m, &mut a, &mut b, s, 3 * n / 4 + 1);
+
+    // Second recursion, on the leading part of what remains
+    let n2 = a.nb_bits().max(b.nb_bits());
+    if can_step(&a, &b, s) && n2 > s + 1 {
+        let p2 = (2 * s + 1).saturating_sub(n2);
+        let (m2, _, _) = half_gcd(&(&a >> p2), &(&b >> p2));
+        if !m2.is_identity() {
+            if let Some((new_a, new_b)) = m2.reduce(&a, &b) {
+                (a, b, m) = (new_a, new_b, m.mul(&m2));
+            }
+        }
+    }
+
+    reduce_until(&mut m, &mut a, &mut b, s, 0);
+    (m, a, b)
+}
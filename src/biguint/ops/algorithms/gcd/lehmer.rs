@@ -0,0 +1,98 @@
+//! lehmer: prediction of several Euclidean steps at once from the leading bits
+//! of the operands.
+
+use super::digit;
+use super::leading_bits;
+use super::Matrix;
+use crate::traits::Digit;
+use crate::BigInt;
+use crate::BigUint;
+
+/// Unimodular matrix `[[u00, u01], [u10, u11]]`, with non-negative entries,
+/// such that `(a, b) = U (a', b')` where `(a', b')` is the reduced pair.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct LehmerMatrix {
+    u: [u32; 4],
+}
+
+impl LehmerMatrix {
+    /// Returns the reduced pair `(a', b')`
+    pub(super) fn reduce<T: Digit>(
+        &self,
+        a: &BigUint<T>,
+        b: &BigUint<T>,
+    ) -> (BigUint<T>, BigUint<T>) {
+        let [u00, u01, u10, u11] = self.u.map(digit::<T>);
+        let new_a = a * u11 - b * u01;
+        let new_b = b * u00 - a * u10;
+        (new_a, new_b)
+    }
+
+    /// Returns the same matrix, acting on the swapped pair `(b, a)`
+    pub(super) fn swapped(&self) -> Self {
+        let [u00, u01, u10, u11] = self.u;
+        Self {
+            u: [u11, u10, u01, u00],
+        }
+    }
+
+    /// Returns `m * self`
+    pub(super) fn left_mul<T: Digit>(&self, m: &Matrix<T>) -> Matrix<T> {
+        let [u00, u01, u10, u11] = self.u.map(digit::<T>);
+        let [m00, m01, m10, m11] = &m.m;
+        Matrix {
+            m: [
+                m00 * u00 + m01 * u10,
+                m00 * u01 + m01 * u11,
+                m10 * u00 + m11 * u10,
+                m10 * u01 + m11 * u11,
+            ],
+        }
+    }
+
+    /// Returns the entries of the inverse matrix, in the same order
+    pub(super) fn inverse_entries<T: Digit>(&self) -> [BigInt<T>; 4] {
+        let [u00, u01, u10, u11] = self.u.map(|u| BigInt::from_unsigned(digit::<T>(u)));
+        [u11, -u01, -u10, u00]
+    }
+}
+
+/// Computes the Lehmer matrix of a pair with `a >= b` and `a` of more than
+/// 64 bits, from the 62 leading bits of `a`. Returns None if not a single step
+/// can be predicted with certainty.
+pub(super) fn lehmer_matrix<T: Digit>(a: &BigUint<T>, b: &BigUint<T>) -> Option<LehmerMatrix> {
+    debug_assert!(a >= b);
+    let shift = a.nb_bits().checked_sub(62)?;
+    let mut x = leading_bits(&a.val, shift) as i128;
+    let mut y = leading_bits(&b.val, shift) as i128;
+
+    // Collins' condition guarantees that the quotients computed on the leading
+    // bits are the true quotients of the full numbers
+    let (mut ca, mut cb, mut cc, mut cd) = (1i128, 0i128, 0i128, 1i128);
+    let mut k = 0;
+    while y > cc {
+        let q = (x + ca - 1) / (y - cc);
+        let s = cb + q * cd;
+        let t = x - q * y;
+        let new_d = ca + q * cc;
+        if s > t || s > u32::MAX as i128 || new_d > u32::MAX as i128 {
+            break;
+        }
+        (x, y) = (y, t);
+        (ca, cb, cc, cd) = (cd, cc, s, new_d);
+        k += 1;
+    }
+
+    if k == 0 {
+        return None;
+    }
+
+    let u = if k % 2 == 0 {
+        [cd, cb, cc, ca]
+    } else {
+        [ca, cc, cb, cd]
+    };
+    Some(LehmerMatrix {
+        u: u.map(|u| u as u32),
+    })
+}
@@ -0,0 +1,300 @@
+//! gcd: computation of the greatest common divisor, optionally along with
+//! Bézout coefficients.
+//!
+//! Small operands go through plain Euclidean steps, medium ones through
+//! Lehmer's algorithm, and large ones through a subquadratic half-gcd.
+
+use crate::traits::Digit;
+use crate::traits::RemDiv;
+use crate::BigInt;
+use crate::BigUint;
+
+mod half_gcd;
+mod lehmer;
+
+use half_gcd::Matrix;
+
+// Above this number of digits, the half-gcd is used to reduce the operands
+#[cfg(debug_assertions)]
+const HALF_GCD_THRESHOLD: usize = 6;
+
+#[cfg(not(debug_assertions))]
+const HALF_GCD_THRESHOLD: usize = 60;
+
+/// Keeps track of the Bézout coefficients of the current pair, with respect
+/// to the original operands `a0` and `b0`, such that
+/// `a = xa * a0 + ya * b0` and `b = xb * a0 + yb * b0`
+struct Cofactors<T: Digit> {
+    xa: BigInt<T>,
+    ya: BigInt<T>,
+    xb: BigInt<T>,
+    yb: BigInt<T>,
+}
+
+impl<T: Digit> Cofactors<T> {
+    fn new() -> Self {
+        Self {
+            xa: BigInt::from_unsigned(T::ONE),
+            ya: BigInt::default(),
+            xb: BigInt::default(),
+            yb: BigInt::from_unsigned(T::ONE),
+        }
+    }
+
+    /// Replaces the current pair `(a, b)` by `(m00 a + m01 b, m10 a + m11 b)`
+    fn apply(&mut self, m00: &BigInt<T>, m01: &BigInt<T>, m10: &BigInt<T>, m11: &BigInt<T>) {
+        let xa = m00 * &self.xa + m01 * &self.xb;
+        let ya = m00 * &self.ya + m01 * &self.yb;
+        let xb = m10 * &self.xa + m11 * &self.xb;
+        let yb = m10 * &self.ya + m11 * &self.yb;
+        (self.xa, self.ya, self.xb, self.yb) = (xa, ya, xb, yb);
+    }
+}
+
+/// Drives the reduction of a pair of integers until one of them is zero
+struct EuclidMachine<T: Digit> {
+    a: BigUint<T>,
+    b: BigUint<T>,
+    cofactors: Option<Cofactors<T>>,
+}
+
+impl<T: Digit> EuclidMachine<T> {
+    fn new(a: &BigUint<T>, b: &BigUint<T>, extended: bool) -> Self {
+        Self {
+            a: a.clone(),
+            b: b.clone(),
+            cofactors: extended.then(Cofactors::new),
+        }
+    }
+
+    fn swap(&mut self) {
+        std::mem::swap(&mut self.a, &mut self.b);
+        if let Some(c) = &mut self.cofactors {
+            std::mem::swap(&mut c.xa, &mut c.xb);
+            std::mem::swap(&mut c.ya, &mut c.yb);
+        }
+    }
+
+    /// Replaces a by a mod b
+    fn division_step(&mut self) {
+        let (q, r) = small_quotient_rem_div(&self.a, &self.b);
+        self.a = r;
+        if let Some(c) = &mut self.cofactors {
+            let q = BigInt::from(q);
+            c.xa -= &q * &c.xb;
+            c.ya -= &q * &c.yb;
+        }
+    }
+
+    /// Performs several Euclidean steps at once, predicted from the leading bits
+    /// of the operands. Returns false if no step could be predicted.
+    fn lehmer_step(&mut self) -> bool {
+        let lehmer = match lehmer::lehmer_matrix(&self.a, &self.b) {
+            Some(lehmer) => lehmer,
+            None => return false,
+        };
+
+        (self.a, self.b) = lehmer.reduce(&self.a, &self.b);
+        if let Some(c) = &mut self.cofactors {
+            let [m00, m01, m10, m11] = lehmer.inverse_entries();
+            c.apply(&m00, &m01, &m10, &m11);
+        }
+        true
+    }
+
+    /// Reduces both operands to about half their size at once. Returns false if
+    /// no reduction could be achieved.
+    fn half_gcd_step(&mut self) -> bool {
+        let (m, a, b) = half_gcd::half_gcd(&self.a, &self.b);
+        if m.is_identity() {
+            return false;
+        }
+
+        (self.a, self.b) = (a, b);
+        if let Some(c) = &mut self.cofactors {
+            let [m00, m01, m10, m11] = m.inverse_entries();
+            c.apply(&m00, &m01, &m10, &m11);
+        }
+        true
+    }
+
+    fn run(&mut self) {
+        loop {
+            if self.a < self.b {
+                self.swap();
+            }
+            if self.b.is_zero() {
+                return;
+            }
+
+            let reduced = if self.a.val.len() > self.b.val.len() + 1 || self.a.nb_bits() <= 64 {
+                false
+            } else if self.b.val.len() >= HALF_GCD_THRESHOLD {
+                self.half_gcd_step() || self.lehmer_step()
+            } else {
+                self.lehmer_step()
+            };
+
+            if !reduced {
+                self.division_step();
+            }
+        }
+    }
+}
+
+#[inline]
+fn digit<T: Digit>(val: u32) -> T {
+    T::decomposition_from_u32(val)[0]
+}
+
+/// Computes the division of n by d, specifically optimized for the case where the
+/// quotient is known to be small, which is the overwhelmingly common case in
+/// Euclid's algorithm.
+fn small_quotient_rem_div<T: Digit>(n: &BigUint<T>, d: &BigUint<T>) -> (BigUint<T>, BigUint<T>) {
+    let n_bits = n.nb_bits();
+    if n_bits <= 64 || n_bits >= d.nb_bits() + 31 {
+        return n.rem_div(d).unwrap();
+    }
+
+    // Underestimate the quotient from the 64 leading bits of n
+    let shift = n_bits - 64;
+    let x = leading_bits(&n.val, shift);
+    let y = leading_bits(&d.val, shift);
+    let mut q = (x as u128 / (y as u128 + 1)) as u64;
+    debug_assert!(q <= u32::MAX as u64);
+
+    let mut r = n - d * digit::<T>(q as u32);
+    while &r >= d {
+        r -= d;
+        q += 1;
+    }
+    (BigUint::from(q), r)
+}
+
+/// Returns the 64 bits of `n >> shift` with lowest weight
+fn leading_bits<T: Digit>(n: &[T], shift: usize) -> u64 {
+    let mut ret = 0u128;
+    for (i, d) in n
+        .iter()
+        .skip(shift / T::NB_BITS)
+        .take(128 / T::NB_BITS)
+        .enumerate()
+    {
+        let d: u64 = match (*d).try_into() {
+            Ok(d) => d,
+            Err(_) => unreachable!(),
+        };
+        ret |= (d as u128) << (i * T::NB_BITS);
+    }
+    (ret >> (shift % T::NB_BITS)) as u64
+}
+
+/// Current implementation of the greatest common divisor
+pub(crate) fn gcd<T: Digit>(a: &BigUint<T>, b: &BigUint<T>) -> BigUint<T> {
+    let mut machine = EuclidMachine::new(a, b, false);
+    machine.run();
+    machine.a
+}
+
+/// Current implementation of the extended greatest common divisor: returns
+/// `(g, x, y)` such that `g = a * x + b * y`
+pub(crate) fn extended_gcd<T: Digit>(
+    a: &BigUint<T>,
+    b: &BigUint<T>,
+) -> (BigUint<T>, BigInt<T>, BigInt<T>) {
+    let mut machine = EuclidMachine::new(a, b, true);
+    machine.run();
+    let c = machine.cofactors.unwrap();
+    (machine.a, c.xa, c.ya)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::traits::{Digit, RemDiv};
+    use crate::{BigInt, BigUint};
+    use typed_test_gen::test_with;
+
+    fn naive_gcd<T: Digit>(a: &BigUint<T>, b: &BigUint<T>) -> BigUint<T> {
+        let (mut a, mut b) = (a.clone(), b.clone());
+        while !b.is_zero() {
+            let r = a.rem(&b).unwrap();
+            (a, b) = (b, r);
+        }
+        a
+    }
+
+    fn check<T: Digit>(a: &BigUint<T>, b: &BigUint<T>) {
+        let g = super::gcd(a, b);
+        assert_eq!(g, naive_gcd(a, b));
+
+        let (g2, x, y) = super::extended_gcd(a, b);
+        assert_eq!(g, g2);
+        let a = BigInt::from(a.clone());
+        let b = BigInt::from(b.clone());
+        assert_eq!(a * x + b * y, BigInt::from(g));
+    }
+
+    fn fibonacci<T: Digit>(n: usize) -> (BigUint<T>, BigUint<T>) {
+        let mut a = BigUint::<T>::new(T::ZERO);
+        let mut b = BigUint::<T>::new(T::ONE);
+        for _ in 0..n {
+            a += &b;
+            std::mem::swap(&mut a, &mut b);
+        }
+        (a, b)
+    }
+
+    #[test_with(u32, u64)]
+    fn small_quotients<T: Digit>() {
+        // Consecutive fibonacci numbers are the worst case of Euclid's algorithm
+        for n in [10, 100, 1000, 5000] {
+            let (a, b) = fibonacci::<T>(n);
+            check(&a, &b);
+            check(&b, &a);
+        }
+    }
+
+    #[test_with(u32, u64)]
+    fn common_factor<T: Digit>() {
+        let (f1, f2) = fibonacci::<T>(3000);
+        let (f3, _) = fibonacci::<T>(2000);
+        let factor = BigUint::<T>::from(vec![T::MAX; 20]) + T::ONE;
+        check(&(&f1 * &factor), &(&f3 * &factor));
+        check(&(&f1 * &f2 * &factor), &(&f2 * &f3));
+        check(&(&f2 << 1000), &(&f3 << 700));
+    }
+
+    #[test_with(u32, u64)]
+    fn unbalanced<T: Digit>() {
+        let (a, b) = fibonacci::<T>(4000);
+        let (c, _) = fibonacci::<T>(300);
+        check(&a, &c);
+        check(&(&a * &b), &c);
+        check(&a, &BigUint::new(T::MAX));
+    }
+
+    #[test_with(u32, u64)]
+    fn zero<T: Digit>() {
+        let (a, _) = fibonacci::<T>(1000);
+        let zero = BigUint::<T>::default();
+        check(&a, &zero);
+        check(&zero, &a);
+        check(&zero, &zero);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test_with(u32, u64)]
+    fn random<T: Digit>()
+    where
+        rand::distributions::Standard: rand::prelude::Distribution<T>,
+    {
+        use crate::gen_random_biguint;
+        for size in [100, 1000, 5000] {
+            let a = gen_random_biguint::<T>(size);
+            let b = gen_random_biguint::<T>(size + 10);
+            let c = gen_random_biguint::<T>(size / 2);
+            check(&a, &b);
+            check(&(&a * &c), &(&b * &c));
+        }
+    }
+}
@@ -1,13 +1,34 @@
 mod add;
 mod div;
+mod gcd;
+mod modpow;
 mod mul;
+mod prime;
+mod radix;
+mod root;
 mod rsub;
 mod sub;
 
 pub(crate) use add::add_assign;
 pub(crate) use div::div;
+pub(crate) use gcd::extended_gcd;
+pub(crate) use gcd::gcd;
+pub(crate) use modpow::modpow;
 pub(crate) use mul::mul;
 pub(crate) use mul::mul_assign_digit;
+pub(crate) use mul::square;
+#[cfg(feature = "rand")]
+pub(crate) use prime::find_prime;
+pub(crate) use prime::is_prime;
+pub(crate) use prime::is_probable_prime;
+pub(crate) use prime::next_prime;
+pub(crate) use prime::prev_prime;
+pub(crate) use radix::from_radix;
+pub(crate) use radix::to_radix;
+pub(crate) use root::is_perfect_power;
+pub(crate) use root::is_perfect_square;
+pub(crate) use root::nth_root;
+pub(crate) use root::sqrt_rem;
 pub(crate) use rsub::rsub_assign;
 pub(crate) use sub::sub_assign;
 
@@ -0,0 +1,191 @@
+//! modpow: modular exponentiation, with Montgomery reduction for odd moduli
+//! and a division based reduction for even ones.
+
+use crate::errors::DivisionError;
+use crate::traits::Digit;
+use crate::traits::DivisionResult;
+use crate::traits::RemDiv;
+use crate::BigUint;
+
+mod montgomery;
+
+pub(crate) use montgomery::Montgomery;
+
+/// Arithmetic modulo a fixed modulus, possibly on a different representation
+/// of the residues (the "domain")
+pub(crate) trait ModularReducer<T: Digit> {
+    /// Converts a (reduced) integer into the domain
+    fn enter_domain(&self, a: &BigUint<T>) -> BigUint<T>;
+
+    /// Converts back an element of the domain into a reduced integer
+    fn leave_domain(&self, a: BigUint<T>) -> BigUint<T>;
+
+    /// The element of the domain representing 1
+    fn one(&self) -> BigUint<T>;
+
+    /// Multiplication of two elements of the domain
+    fn mul(&self, a: &BigUint<T>, b: &BigUint<T>) -> BigUint<T>;
+
+    /// Square of an element of the domain
+    fn square(&self, a: &BigUint<T>) -> BigUint<T>;
+}
+
+/// Reduction by an actual division after each multiplication
+struct DivisionReducer<'a, T: Digit> {
+    modulus: &'a BigUint<T>,
+}
+
+impl<'a, T: Digit> ModularReducer<T> for DivisionReducer<'a, T> {
+    fn enter_domain(&self, a: &BigUint<T>) -> BigUint<T> {
+        a.clone()
+    }
+
+    fn leave_domain(&self, a: BigUint<T>) -> BigUint<T> {
+        a
+    }
+
+    fn one(&self) -> BigUint<T> {
+        BigUint::new(T::ONE)
+    }
+
+    fn mul(&self, a: &BigUint<T>, b: &BigUint<T>) -> BigUint<T> {
+        (a * b).rem(self.modulus).unwrap()
+    }
+
+    fn square(&self, a: &BigUint<T>) -> BigUint<T> {
+        a.square().rem(self.modulus).unwrap()
+    }
+}
+
+/// Size of the window to use for an exponent of `nb_bits` bits
+fn window_size(nb_bits: usize) -> usize {
+    match nb_bits {
+        0..=24 => 1,
+        25..=80 => 3,
+        81..=240 => 4,
+        241..=672 => 5,
+        673..=1792 => 6,
+        _ => 7,
+    }
+}
+
+/// Left-to-right sliding window exponentiation, `base` and the result being
+/// elements of the domain of `reducer`
+pub(crate) fn sliding_window_pow<T: Digit, R: ModularReducer<T>>(
+    reducer: &R,
+    base: &BigUint<T>,
+    exp: &BigUint<T>,
+) -> BigUint<T> {
+    let nb_bits = exp.nb_bits();
+    let window = window_size(nb_bits);
+
+    // Precomputation of the odd powers of the base
+    let mut odd_powers = vec![base.clone()];
+    if window > 1 {
+        let base_squared = reducer.square(&odd_powers[0]);
+        for i in 1..1 << (window - 1) {
+            let next = reducer.mul(&odd_powers[i - 1], &base_squared);
+            odd_powers.push(next);
+        }
+    }
+
+    let mut ret = reducer.one();
+    let mut i = nb_bits;
+    while i > 0 {
+        if !exp.bit(i - 1) {
+            ret = reducer.square(&ret);
+            i -= 1;
+            continue;
+        }
+
+        // Longest window ending with a 1 bit
+        let mut j = i.saturating_sub(window);
+        while !exp.bit(j) {
+            j += 1;
+        }
+
+        let mut value = 0;
+        for b in (j..i).rev() {
+            ret = reducer.square(&ret);
+            value = (value << 1) | exp.bit(b) as usize;
+        }
+        ret = reducer.mul(&ret, &odd_powers[value >> 1]);
+        i = j;
+    }
+
+    ret
+}
+
+/// Modular exponentiation of an already reduced base
+fn reduced_modpow<T: Digit, R: ModularReducer<T>>(
+    reducer: &R,
+    base: &BigUint<T>,
+    exp: &BigUint<T>,
+) -> BigUint<T> {
+    let base = reducer.enter_domain(base);
+    reducer.leave_domain(sliding_window_pow(reducer, &base, exp))
+}
+
+/// Current implementation of modular exponentiation
+pub(crate) fn modpow<T: Digit>(
+    base: &BigUint<T>,
+    exp: &BigUint<T>,
+    modulus: &BigUint<T>,
+) -> DivisionResult<BigUint<T>> {
+    if modulus.is_zero() {
+        return Err(DivisionError::DivisionByZero);
+    }
+    if *modulus == BigUint::new(T::ONE) {
+        return Ok(BigUint::default());
+    }
+
+    let base = base.rem(modulus)?;
+    if modulus.val[0] & T::ONE == T::ONE {
+        let reducer = Montgomery::new(modulus);
+        Ok(reduced_modpow(&reducer, &base, exp))
+    } else {
+        let reducer = DivisionReducer { modulus };
+        Ok(reduced_modpow(&reducer, &base, exp))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::traits::{Digit, Pow, RemDiv};
+    use crate::BigUint;
+    use typed_test_gen::test_with;
+
+    fn check<T: Digit>(base: &BigUint<T>, exp: usize, modulus: &BigUint<T>) {
+        let expected = base.pow(exp).rem(modulus).unwrap();
+        let exp = BigUint::<T>::from(exp as u64);
+        assert_eq!(super::modpow(base, &exp, modulus).unwrap(), expected);
+    }
+
+    #[test_with(u32, u64)]
+    fn against_pow<T: Digit>() {
+        let base = BigUint::<T>::from(vec![T::MAX; 7]) - T::TWO;
+        let odd = BigUint::<T>::from(vec![T::MAX; 5]);
+        let even = &odd - T::ONE;
+        let big_odd = (BigUint::<T>::from(vec![T::MAX; 30]) << 7) + T::ONE;
+        for exp in [0, 1, 2, 3, 17, 64, 255, 1000] {
+            check(&base, exp, &odd);
+            check(&base, exp, &even);
+            check(&base, exp, &big_odd);
+            check(&base, exp, &(&big_odd + T::ONE));
+        }
+    }
+
+    #[test_with(u32, u64)]
+    fn fermat<T: Digit>() {
+        // 2^521 - 1 is a Mersenne prime
+        let p = (BigUint::<T>::new(T::ONE) << 521) - T::ONE;
+        let exp = &p - T::ONE;
+        for a in [2u32, 3, 12345, u32::MAX] {
+            let a = BigUint::<T>::from(a);
+            assert_eq!(
+                super::modpow(&a, &exp, &p).unwrap(),
+                BigUint::<T>::new(T::ONE)
+            );
+        }
+    }
+}
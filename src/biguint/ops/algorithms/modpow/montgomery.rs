@@ -0,0 +1,152 @@
+//! montgomery: Montgomery representation of residues modulo an odd integer,
+//! in which the reduction after a multiplication needs no division.
+
+use super::ModularReducer;
+use crate::biguint::ops::algorithms::add_assign;
+use crate::biguint::ops::algorithms::gcd::extended_gcd;
+use crate::biguint::ops::algorithms::mul::single_digit_add_assign_mul;
+use crate::traits::Digit;
+use crate::traits::DoubleDigit;
+use crate::traits::RemDiv;
+use crate::BigInt;
+use crate::BigUint;
+
+// Above this number of digits, the reduction is done with full multiplications
+// instead of digit by digit
+#[cfg(debug_assertions)]
+const MUL_REDUCTION_THRESHOLD: usize = 8;
+
+#[cfg(not(debug_assertions))]
+const MUL_REDUCTION_THRESHOLD: usize = 80;
+
+/// Residues modulo `modulus` (of `k` digits) are represented as `a * R mod modulus`
+/// with `R = 2^(k * T::NB_BITS)`.
+pub(crate) struct Montgomery<'a, T: Digit> {
+    modulus: &'a BigUint<T>,
+    k: usize,
+    // -modulus^-1 modulo 2^T::NB_BITS
+    m_prime: T,
+    // -modulus^-1 modulo R, only for the multiplication based reduction
+    m_inv: Option<BigUint<T>>,
+    // R^2 modulo modulus
+    r2: BigUint<T>,
+}
+
+/// Inverse of an odd digit modulo 2^T::NB_BITS, by Newton's iteration
+fn digit_inverse<T: Digit>(d: T) -> T {
+    debug_assert!(d & T::ONE == T::ONE);
+    let low_mul = |a: T, b: T| (a.to_double() * b.to_double()).truncate_upper();
+
+    // Correct modulo 2, and each iteration doubles the number of correct bits
+    let mut x = T::ONE;
+    let mut correct_bits = 1;
+    while correct_bits < T::NB_BITS {
+        x = low_mul(x, T::TWO.overflowing_sub(low_mul(d, x)).0);
+        correct_bits *= 2;
+    }
+    x
+}
+
+impl<'a, T: Digit> Montgomery<'a, T> {
+    pub(crate) fn new(modulus: &'a BigUint<T>) -> Self {
+        let k = modulus.val.len();
+        let m_prime = T::ZERO.overflowing_sub(digit_inverse(modulus.val[0])).0;
+
+        let r = BigUint::new(T::ONE) << (k * T::NB_BITS);
+        let m_inv = (k >= MUL_REDUCTION_THRESHOLD).then(|| {
+            let (_, x, _) = extended_gcd(modulus, &r);
+            // x is the inverse of modulus, we want its opposite modulo R
+            let x = x.rem(&BigInt::from(r.clone())).unwrap().uint;
+            &r - &x
+        });
+
+        let r2 = (BigUint::new(T::ONE) << (2 * k * T::NB_BITS))
+            .rem(modulus)
+            .unwrap();
+
+        Self {
+            modulus,
+            k,
+            m_prime,
+            m_inv,
+            r2,
+        }
+    }
+
+    /// Computes `t / R mod modulus`, for `t < modulus * R`
+    fn reduce(&self, t: BigUint<T>) -> BigUint<T> {
+        let mut ret = match &self.m_inv {
+            Some(m_inv) => self.reduce_with_mul(t, m_inv),
+            None => self.reduce_digitwise(t),
+        };
+        if &ret >= self.modulus {
+            ret -= self.modulus;
+        }
+        ret
+    }
+
+    fn reduce_digitwise(&self, t: BigUint<T>) -> BigUint<T> {
+        let k = self.k;
+        let mut t = t.val;
+        t.resize(2 * k + 1, T::ZERO);
+
+        // Each step cancels the lowest digit of t
+        for i in 0..k {
+            let u = (t[i].to_double() * self.m_prime.to_double()).truncate_upper();
+            let high = t[i + k];
+            single_digit_add_assign_mul(&mut t[i..=i + k], &self.modulus.val, u);
+            let carry = add_assign(&mut t[i + k..], &[high]);
+            debug_assert!(!carry);
+        }
+
+        BigUint::from(t.split_off(k))
+    }
+
+    fn reduce_with_mul(&self, t: BigUint<T>, m_inv: &BigUint<T>) -> BigUint<T> {
+        let k = self.k;
+        let low = BigUint::from(t.val[..k.min(t.val.len())].to_vec());
+        let mut u = low * m_inv;
+        u.val.truncate(k);
+        u.remove_leading_zeros();
+
+        // t + u * modulus is a multiple of R
+        (t + u * self.modulus) >> (k * T::NB_BITS)
+    }
+}
+
+impl<'a, T: Digit> ModularReducer<T> for Montgomery<'a, T> {
+    fn enter_domain(&self, a: &BigUint<T>) -> BigUint<T> {
+        self.reduce(a * &self.r2)
+    }
+
+    fn leave_domain(&self, a: BigUint<T>) -> BigUint<T> {
+        self.reduce(a)
+    }
+
+    fn one(&self) -> BigUint<T> {
+        self.reduce(self.r2.clone())
+    }
+
+    fn mul(&self, a: &BigUint<T>, b: &BigUint<T>) -> BigUint<T> {
+        self.reduce(a * b)
+    }
+
+    fn square(&self, a: &BigUint<T>) -> BigUint<T> {
+        self.reduce(a.square())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::digit_inverse;
+
+    #[test]
+    fn inverse() {
+        for d in [1u32, 3, 5, 12345, u32::MAX] {
+            assert_eq!(d.wrapping_mul(digit_inverse(d)), 1);
+        }
+        for d in [1u64, 3, 5, 1234567891011, u64::MAX] {
+            assert_eq!(d.wrapping_mul(digit_inverse(d)), 1);
+        }
+    }
+}
@@ -5,6 +5,7 @@ use std::ops::DerefMut;
 use super::super::add_assign;
 use super::super::sub_assign;
 use super::schoolbook_mul;
+use super::schoolbook_square;
 
 // Below this number of digits, multiplication is schoolbook
 #[cfg(debug_assertions)]
@@ -32,6 +33,12 @@ pub(super) fn karatsuba<T: Digit>(ret: &mut [T], rhs: &[T], lhs: &[T]) {
     }
 }
 
+/// squares x, puts the result in ret (which doesn't have to be filled with zeros)
+pub(super) fn karatsuba_square<T: Digit>(ret: &mut [T], x: &[T]) {
+    let mut buff = allocate_buffer(x.len().next_power_of_two() << 1);
+    symetric_karatsuba_square(ret, x, &mut buff);
+}
+
 /// multiplies big and small, puts the result in ret.
 ///
 /// we assume big is larger than small, and that ret is filled with zeros
@@ -141,3 +148,54 @@ fn symetric_karatsuba<T: Digit>(ret: &mut [T], x: &[T], y: &[T], buff: &mut [T])
     add_assign(&mut ret[half_size..], &z0[half_size..]);
     sub_assign(&mut ret[half_size..], &z0);
 }
+
+/// squares x, puts the result in ret: same as `symetric_karatsuba`, with the
+/// three recursive products being squares
+///
+/// ret doesn't have to be filled with zeros
+fn symetric_karatsuba_square<T: Digit>(ret: &mut [T], x: &[T], buff: &mut [T]) {
+    // Early exit
+    if exit_karatsuba(x.len()) {
+        schoolbook_square(ret, x);
+        return;
+    }
+
+    let size = x.len();
+    let half_size = (size >> 1) + (size % 2);
+    let small_half_size = size >> 1;
+    let size = half_size << 1;
+
+    debug_assert_eq!(ret.len(), 2 * x.len());
+    debug_assert!(buff.len() >= 2 * size);
+
+    let (buff, sub_buff) = buff.split_at_mut(size);
+    let (x0, x1) = x.split_at(half_size);
+
+    // Compute x0 + x1 in buff
+    let x_cross = &mut buff[..half_size];
+    x_cross.copy_from_slice(x0);
+    let x_carry = add_assign(x_cross, x1);
+
+    // Compute z1 in ret
+    let z1 = &mut ret[half_size..half_size + size + 2];
+    symetric_karatsuba_square(&mut z1[..size], x_cross, sub_buff);
+    z1[size] = T::from(x_carry);
+    if x_carry {
+        add_assign(&mut z1[half_size..], x_cross);
+        add_assign(&mut z1[half_size..], x_cross);
+    }
+
+    // Compute z2 in buff
+    let z2 = &mut buff[..2 * small_half_size];
+    symetric_karatsuba_square(z2, x1, sub_buff);
+    ret[half_size + size + 1..].copy_from_slice(&z2[half_size + 1..]);
+    add_assign(&mut ret[size..], &z2[..half_size + 1]);
+    sub_assign(&mut ret[half_size..], z2);
+
+    // Compute z0 in buff
+    let z0 = &mut buff[..size];
+    symetric_karatsuba_square(z0, x0, sub_buff);
+    ret[..half_size].copy_from_slice(&z0[..half_size]);
+    add_assign(&mut ret[half_size..], &z0[half_size..]);
+    sub_assign(&mut ret[half_size..], z0);
+}
@@ -12,6 +12,8 @@ use super::u32_ptrs_aligned_3;
 use crate::traits::ToPtr;
 
 mod karatsuba;
+mod ntt;
+mod toom;
 
 #[cfg(all(feature = "unsafe", target_arch = "x86_64"))]
 mod x86_64;
@@ -29,6 +31,37 @@ fn schoolbook_mul<T: Digit>(ret: &mut [T], rhs: &[T], lhs: &[T]) {
     }
 }
 
+/// puts the square of a in ret, computing the products a[i] * a[j] only once
+fn schoolbook_square<T: Digit>(ret: &mut [T], a: &[T]) {
+    debug_assert_eq!(ret.len(), 2 * a.len());
+    ret.fill(T::ZERO);
+
+    // Products a[i] * a[j] with i < j
+    for i in 1..a.len() {
+        single_digit_add_assign_mul(&mut ret[2 * i - 1..], &a[i..], a[i - 1]);
+    }
+
+    // They appear twice in the square
+    let mut top = T::ZERO;
+    for d in ret.iter_mut() {
+        let next_top = *d >> (T::NB_BITS - 1);
+        *d <<= 1;
+        *d |= top;
+        top = next_top;
+    }
+
+    // Products a[i] * a[i]
+    let mut carry = T::ZERO;
+    for (r, d) in ret.chunks_exact_mut(2).zip(a.iter()) {
+        let (lo, hi) = (d.to_double() * d.to_double()).split();
+        let full = r[0].to_double() + lo.to_double() + carry.to_double();
+        (r[0], carry) = full.split();
+        let full = r[1].to_double() + hi.to_double() + carry.to_double();
+        (r[1], carry) = full.split();
+    }
+    debug_assert_eq!(carry, T::ZERO);
+}
+
 /// Compute the multiplication of `rhs` by `b` and writes the result to `ret`
 fn single_digit_mul<T: Digit>(ret: &mut [T], rhs: &[T], b: T) {
     debug_assert!(ret.len() > rhs.len());
@@ -74,7 +107,7 @@ fn single_digit_mul<T: Digit>(ret: &mut [T], rhs: &[T], b: T) {
 }
 
 /// Compute the multiplication of `rhs` by `b` and adds the result to `ret`
-fn single_digit_add_assign_mul<T: Digit>(ret: &mut [T], rhs: &[T], b: T) {
+pub(crate) fn single_digit_add_assign_mul<T: Digit>(ret: &mut [T], rhs: &[T], b: T) {
     debug_assert!(ret.len() > rhs.len());
 
     // Specifically for u32 digits, we accelerate by reinterpreting arrays as u64
@@ -233,12 +266,46 @@ pub(crate) fn mul<T: Digit>(ret: &mut [T], rhs: &[T], lhs: &[T]) {
         return;
     }
 
-    if karatsuba::exit_karatsuba(lhs.len().min(rhs.len())) {
+    if std::ptr::eq(rhs, lhs) {
+        square(ret, rhs);
+        return;
+    }
+
+    let size = lhs.len().min(rhs.len());
+    if karatsuba::exit_karatsuba(size) {
         // small input: got to schoolbook
         schoolbook_mul(ret, rhs, lhs);
-    } else {
+    } else if toom::exit_toom_3::<T>(size) {
         ret.fill(T::ZERO);
         karatsuba::karatsuba(ret, rhs, lhs);
+    } else if ntt::exit_ntt::<T>(size) {
+        toom::toom(ret, rhs, lhs);
+    } else {
+        ntt::ntt_mul(ret, rhs, lhs);
+    }
+}
+
+/// Current implementation of squaring
+pub(crate) fn square<T: Digit>(ret: &mut [T], a: &[T]) {
+    debug_assert_eq!(ret.len(), 2 * a.len());
+
+    // Same as multiplication, the u64 squaring is called back from mul_u32
+    #[cfg(feature = "unsafe")]
+    if let (Some(ret_cast), Some(a_cast)) = (ret.to_mut_ptr::<u32>(), a.to_ptr::<u32>()) {
+        unsafe { mul_u32(ret, ret_cast, a, a_cast, a, a_cast) };
+        return;
+    }
+
+    // Toom-Cook and NTT detect squarings by their operands being the same slice
+    let size = a.len();
+    if karatsuba::exit_karatsuba(size) {
+        schoolbook_square(ret, a);
+    } else if toom::exit_toom_3::<T>(size) {
+        karatsuba::karatsuba_square(ret, a);
+    } else if ntt::exit_ntt::<T>(size) {
+        toom::toom(ret, a, a);
+    } else {
+        ntt::ntt_mul(ret, a, a);
     }
 }
 
@@ -0,0 +1,362 @@
+//! ntt: multiplication by number theoretic transforms. Operands are cut in
+//! 64 bits words, seen as the coefficients of polynomials. Their product is
+//! computed modulo three primes of the form c * 2^k + 1 with fast transforms,
+//! and the actual coefficients are recovered by the chinese remainder theorem.
+//!
+//! The product of the three primes is above 2^183, so the coefficients are
+//! exact as long as the transform length stays below 2^55.
+
+use crate::traits::Digit;
+
+// Below this number of digits, multiplication is Toom-Cook
+#[cfg(debug_assertions)]
+const NTT_THRESHOLD_U32: usize = 64;
+#[cfg(debug_assertions)]
+const NTT_THRESHOLD_U64: usize = 32;
+
+#[cfg(not(debug_assertions))]
+const NTT_THRESHOLD_U32: usize = 1500;
+#[cfg(not(debug_assertions))]
+const NTT_THRESHOLD_U64: usize = 12000;
+
+/// Primes of the form c * 2^k + 1 (k >= 55), with a generator of their
+/// multiplicative group
+const PRIMES: [(u64, u64); 3] = [
+    (4179340454199820289, 3), // 29 * 2^57 + 1
+    (2485986994308513793, 5), // 69 * 2^55 + 1
+    (1945555039024054273, 5), // 27 * 2^56 + 1
+];
+
+/// Base 2 logarithm of the largest supported transform length
+const MAX_LOG_LENGTH: u32 = 55;
+
+#[inline]
+pub(super) fn exit_ntt<T: Digit>(size: usize) -> bool {
+    match T::NB_BITS {
+        32 => size < NTT_THRESHOLD_U32,
+        _ => size < NTT_THRESHOLD_U64,
+    }
+}
+
+/// Arithmetic modulo an odd prime below 2^62, with Montgomery multiplication
+#[derive(Clone, Copy)]
+struct Field {
+    p: u64,
+    /// 1 / p modulo 2^64
+    p_inv: u64,
+    /// 2^128 modulo p
+    r2: u64,
+}
+
+impl Field {
+    fn new(p: u64) -> Field {
+        // Newton iteration: each step doubles the number of correct bits
+        let mut inv = p;
+        for _ in 0..5 {
+            inv = inv.wrapping_mul(2u64.wrapping_sub(p.wrapping_mul(inv)));
+        }
+        let r = (1u128 << 64) % p as u128;
+        let r2 = (r * r) % p as u128;
+        Field {
+            p,
+            p_inv: inv,
+            r2: r2 as u64,
+        }
+    }
+
+    /// a * b / 2^64 modulo p (a * b has to be below p * 2^64)
+    #[inline]
+    fn mul(self, a: u64, b: u64) -> u64 {
+        let t = a as u128 * b as u128;
+        // m * p has the same lower half as t, so only the upper halves matter
+        let m = (t as u64).wrapping_mul(self.p_inv);
+        let mp = ((m as u128 * self.p as u128) >> 64) as u64;
+        let (u, borrow) = ((t >> 64) as u64).overflowing_sub(mp);
+        u.wrapping_add(self.p & (borrow as u64).wrapping_neg())
+    }
+
+    // The corrections are written without branches, as they are taken at
+    // random in the transforms
+
+    #[inline]
+    fn add(self, a: u64, b: u64) -> u64 {
+        self.sub(a, self.p - b)
+    }
+
+    #[inline]
+    fn sub(self, a: u64, b: u64) -> u64 {
+        let (d, borrow) = a.overflowing_sub(b);
+        d.wrapping_add(self.p & (borrow as u64).wrapping_neg())
+    }
+
+    /// Montgomery representation of a (ie. a * 2^64 modulo p)
+    #[inline]
+    fn montgomery(self, a: u64) -> u64 {
+        self.mul(a % self.p, self.r2)
+    }
+
+    /// Power of an element in Montgomery representation
+    fn pow(self, mut base: u64, mut exp: u64) -> u64 {
+        let mut ret = self.montgomery(1);
+        while exp > 0 {
+            if exp & 1 == 1 {
+                ret = self.mul(ret, base);
+            }
+            base = self.mul(base, base);
+            exp >>= 1;
+        }
+        ret
+    }
+
+    /// Montgomery representation of the inverse of a
+    fn inverse(self, a: u64) -> u64 {
+        self.pow(self.montgomery(a), self.p - 2)
+    }
+
+    /// Powers of the roots of unity in Montgomery representation:
+    /// `table[len + j]` is w^j, w being of order 2 * len
+    fn roots(self, n: usize, generator: u64, inverse: bool) -> Vec<u64> {
+        let mut table = vec![0; n.max(2)];
+        let half = n.max(2) / 2;
+
+        let order = (self.p - 1) / (2 * half as u64);
+        let exp = if inverse { self.p - 1 - order } else { order };
+        let w = self.pow(self.montgomery(generator), exp);
+        let mut x = self.montgomery(1);
+        for t in table[half..].iter_mut() {
+            *t = x;
+            x = self.mul(x, w);
+        }
+
+        // The roots of smaller orders are squares of the larger ones
+        let mut len = half / 2;
+        while len > 0 {
+            for j in 0..len {
+                table[len + j] = table[2 * len + 2 * j];
+            }
+            len /= 2;
+        }
+        table
+    }
+
+    /// Decimation in frequency transform: natural order in, bit-reversed order out
+    fn forward(self, a: &mut [u64], roots: &[u64]) {
+        let mut len = a.len() / 2;
+        while len > 0 {
+            for block in a.chunks_exact_mut(2 * len) {
+                let (lo, hi) = block.split_at_mut(len);
+                for ((x, y), w) in lo.iter_mut().zip(hi.iter_mut()).zip(&roots[len..]) {
+                    let (u, v) = (*x, *y);
+                    *x = self.add(u, v);
+                    *y = self.mul(self.sub(u, v), *w);
+                }
+            }
+            len /= 2;
+        }
+    }
+
+    /// Decimation in time transform: bit-reversed order in, natural order out
+    fn backward(self, a: &mut [u64], roots: &[u64]) {
+        let mut len = 1;
+        while len < a.len() {
+            for block in a.chunks_exact_mut(2 * len) {
+                let (lo, hi) = block.split_at_mut(len);
+                for ((x, y), w) in lo.iter_mut().zip(hi.iter_mut()).zip(&roots[len..]) {
+                    let (u, v) = (*x, self.mul(*y, *w));
+                    *x = self.add(u, v);
+                    *y = self.sub(u, v);
+                }
+            }
+            len *= 2;
+        }
+    }
+
+    /// Cyclic convolution of length n of a and b (of a with itself if b is
+    /// None), modulo p
+    fn convolution(self, generator: u64, a: &[u64], b: Option<&[u64]>, n: usize) -> Vec<u64> {
+        let reduce = |x: &[u64]| -> Vec<u64> {
+            let mut ret: Vec<u64> = x.iter().map(|w| w % self.p).collect();
+            ret.resize(n, 0);
+            ret
+        };
+
+        let roots = self.roots(n, generator, false);
+        let mut fa = reduce(a);
+        self.forward(&mut fa, &roots);
+        if let Some(b) = b {
+            let mut fb = reduce(b);
+            self.forward(&mut fb, &roots);
+            for (x, y) in fa.iter_mut().zip(fb.iter()) {
+                *x = self.mul(*x, *y);
+            }
+        } else {
+            for x in fa.iter_mut() {
+                *x = self.mul(*x, *x);
+            }
+        }
+
+        let roots = self.roots(n, generator, true);
+        self.backward(&mut fa, &roots);
+
+        // The pointwise products left a factor 1 / 2^64, and the transforms a
+        // factor n: correct both at once
+        let n_inv = self.p - (self.p - 1) / n as u64;
+        let scale = self.mul(self.montgomery(n_inv), self.r2);
+        for x in fa.iter_mut() {
+            *x = self.mul(*x, scale);
+        }
+        fa
+    }
+}
+
+/// Packs digits in 64 bits words
+fn words<T: Digit>(a: &[T]) -> Vec<u64> {
+    a.chunks(64 / T::NB_BITS)
+        .map(|chunk| {
+            chunk.iter().enumerate().fold(0, |acc, (i, d)| {
+                let d: u64 = (*d).try_into().ok().unwrap();
+                acc | (d << (i * T::NB_BITS))
+            })
+        })
+        .collect()
+}
+
+/// Adds a 192 bits integer to another one
+#[inline]
+fn add_192(acc: &mut [u64; 3], x: [u64; 3]) {
+    let mut carry = 0u128;
+    for (a, x) in acc.iter_mut().zip(x) {
+        let s = *a as u128 + x as u128 + carry;
+        *a = s as u64;
+        carry = s >> 64;
+    }
+}
+
+/// multiplies rhs and lhs, puts the result in ret (which doesn't have to be
+/// filled with zeros). When both are the same slice, only one operand is
+/// transformed.
+pub(super) fn ntt_mul<T: Digit>(ret: &mut [T], rhs: &[T], lhs: &[T]) {
+    debug_assert_eq!(ret.len(), rhs.len() + lhs.len());
+    let a = words(rhs);
+    let b = (!std::ptr::eq(rhs, lhs)).then(|| words(lhs));
+    let size = a.len() + b.as_ref().map_or(a.len(), |b| b.len());
+    let n = (size - 1).next_power_of_two();
+    assert!(
+        n.trailing_zeros() <= MAX_LOG_LENGTH,
+        "Operands are too large for the multiplication"
+    );
+
+    let fields = PRIMES.map(|(p, _)| Field::new(p));
+    let residues: Vec<Vec<u64>> = fields
+        .iter()
+        .zip(PRIMES)
+        .map(|(f, (_, generator))| f.convolution(generator, &a, b.as_deref(), n))
+        .collect();
+
+    // Chinese remainder theorem (Garner's algorithm): each coefficient is
+    // x1 + p1 * (t2 + p2 * t3)
+    let [f1, f2, f3] = fields;
+    let p1_inv = f2.inverse(f1.p);
+    let p1_mod_p3 = f3.montgomery(f1.p);
+    let p1_p2 = f1.p as u128 * f2.p as u128;
+    let p1_p2_inv = f3.inverse((p1_p2 % f3.p as u128) as u64);
+    let (p1_p2_lo, p1_p2_hi) = (p1_p2 as u64, (p1_p2 >> 64) as u64);
+
+    let mut acc = [0u64; 3];
+    let mut out = Vec::with_capacity(size);
+    let coefficients = residues[0].iter().zip(&residues[1]).zip(&residues[2]);
+    for ((x1, x2), x3) in coefficients.take(size - 1) {
+        let t2 = f2.mul(f2.sub(*x2, x1 % f2.p), p1_inv);
+        let t3 = f3.sub(*x3, x1 % f3.p);
+        let t3 = f3.mul(f3.sub(t3, f3.mul(t2, p1_mod_p3)), p1_p2_inv);
+
+        let lo = p1_p2_lo as u128 * t3 as u128;
+        let hi = p1_p2_hi as u128 * t3 as u128;
+        let mid = (lo >> 64) + (hi as u64 as u128);
+        let mut x = [lo as u64, mid as u64, ((hi >> 64) + (mid >> 64)) as u64];
+        let low = *x1 as u128 + f1.p as u128 * t2 as u128;
+        add_192(&mut x, [low as u64, (low >> 64) as u64, 0]);
+        add_192(&mut acc, x);
+
+        out.push(acc[0]);
+        acc = [acc[1], acc[2], 0];
+    }
+    out.push(acc[0]);
+
+    for (chunk, w) in ret.chunks_mut(64 / T::NB_BITS).zip(out) {
+        for (r, d) in chunk.iter_mut().zip(T::decomposition_from_u64(w)) {
+            *r = d;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_util::TestRng;
+    use crate::traits::Digit;
+    use crate::BigUint;
+    use typed_test_gen::test_with;
+
+    fn check<T: Digit>(a: &[T], b: &[T]) {
+        let mut expected = vec![T::ZERO; a.len() + b.len()];
+        super::super::schoolbook_mul(&mut expected, a, b);
+        let mut ret = vec![T::MAX; a.len() + b.len()];
+        super::ntt_mul(&mut ret, a, b);
+        assert_eq!(ret, expected);
+    }
+
+    fn operand<T: Digit>(n: usize, seed: u64) -> Vec<T> {
+        TestRng::new(seed).digits(n)
+    }
+
+    #[test]
+    fn field() {
+        for (p, g) in super::PRIMES {
+            let f = super::Field::new(p);
+            assert_eq!(p.wrapping_mul(f.p_inv), 1);
+            let one = f.montgomery(1);
+            assert_eq!(f.mul(f.montgomery(12345), f.inverse(12345)), one);
+            // g generates the whole group, so its square root of unity is -1
+            let g = f.montgomery(g);
+            assert_eq!(f.pow(g, (p - 1) / 2), f.montgomery(p - 1));
+        }
+    }
+
+    #[test_with(u32, u64)]
+    fn small_sizes<T: Digit>() {
+        for (n, m) in [(1, 1), (2, 1), (3, 3), (5, 2), (16, 16), (17, 15), (40, 3)] {
+            check(&operand::<T>(n, 1), &operand::<T>(m, 2));
+        }
+    }
+
+    #[test_with(u32, u64)]
+    fn largest_coefficients<T: Digit>() {
+        // All digits at MAX gives the largest possible convolution terms
+        for (n, m) in [(100, 100), (301, 257), (1000, 10)] {
+            check(&vec![T::MAX; n], &vec![T::MAX; m]);
+        }
+    }
+
+    #[test_with(u32, u64)]
+    fn squares<T: Digit>() {
+        for n in [1, 2, 17, 100, 301] {
+            for a in [operand::<T>(n, 5), vec![T::MAX; n]] {
+                let mut expected = vec![T::ZERO; 2 * n];
+                super::super::schoolbook_mul(&mut expected, &a, &a.clone());
+                let mut ret = vec![T::MAX; 2 * n];
+                super::ntt_mul(&mut ret, &a, &a);
+                assert_eq!(ret, expected);
+            }
+        }
+    }
+
+    #[test_with(u32, u64)]
+    fn against_toom<T: Digit>() {
+        let a = BigUint::from(operand::<T>(3000, 3));
+        let b = BigUint::from(operand::<T>(1700, 4));
+        let mut expected = vec![T::ZERO; 4700];
+        super::super::toom::toom(&mut expected, &a.val, &b.val);
+        assert_eq!((&a * &b).val, expected);
+        assert_eq!((&a * &a) - (&b * &b), (&a + &b) * (&a - &b));
+    }
+}
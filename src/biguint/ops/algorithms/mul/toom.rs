@@ -0,0 +1,324 @@
+//! toom: Toom-Cook multiplication. Operands are split in 3 or 4 pieces, seen
+//! as the coefficients of polynomials. These polynomials are evaluated at a few
+//! small points, the values are multiplied recursively, and the coefficients of
+//! the product are recovered by interpolation.
+//!
+//! Operands of different lengths are handled by the unbalanced Toom-3.2 variant
+//! (also known as Toom-2.5), or by cutting the largest one in chunks.
+
+use super::super::add_assign;
+use super::mul;
+use crate::traits::{Digit, DoubleDigit};
+use crate::{BigInt, BigUint};
+
+// Below this number of digits, multiplication is Karatsuba
+#[cfg(debug_assertions)]
+const TOOM_3_THRESHOLD_U32: usize = 12;
+#[cfg(debug_assertions)]
+const TOOM_3_THRESHOLD_U64: usize = 12;
+
+#[cfg(not(debug_assertions))]
+const TOOM_3_THRESHOLD_U32: usize = 400;
+#[cfg(not(debug_assertions))]
+const TOOM_3_THRESHOLD_U64: usize = 250;
+
+// Below this number of digits, multiplication is Toom-3. With u32 digits,
+// NTT takes over before Toom-4 gets faster than Toom-3, so release builds only
+// reach Toom-4 for them through the tests that call it directly.
+#[cfg(debug_assertions)]
+const TOOM_4_THRESHOLD_U32: usize = 24;
+#[cfg(debug_assertions)]
+const TOOM_4_THRESHOLD_U64: usize = 24;
+
+#[cfg(not(debug_assertions))]
+const TOOM_4_THRESHOLD_U32: usize = usize::MAX;
+#[cfg(not(debug_assertions))]
+const TOOM_4_THRESHOLD_U64: usize = 800;
+
+#[inline]
+pub(super) fn exit_toom_3<T: Digit>(size: usize) -> bool {
+    match T::NB_BITS {
+        32 => size < TOOM_3_THRESHOLD_U32,
+        _ => size < TOOM_3_THRESHOLD_U64,
+    }
+}
+
+#[inline]
+fn exit_toom_4<T: Digit>(size: usize) -> bool {
+    match T::NB_BITS {
+        32 => size < TOOM_4_THRESHOLD_U32,
+        _ => size < TOOM_4_THRESHOLD_U64,
+    }
+}
+
+/// multiplies rhs and lhs, puts the result in ret (which doesn't have to be
+/// filled with zeros)
+pub(super) fn toom<T: Digit>(ret: &mut [T], rhs: &[T], lhs: &[T]) {
+    if rhs.len() < lhs.len() {
+        return toom(ret, lhs, rhs);
+    }
+    debug_assert_eq!(ret.len(), rhs.len() + lhs.len());
+
+    ret.fill(T::ZERO);
+    if rhs.len() >= 2 * lhs.len() {
+        chunked_mul(ret, rhs, lhs);
+    } else if 3 * rhs.len() >= 4 * lhs.len() {
+        toom_32(ret, rhs, lhs);
+    } else if exit_toom_4::<T>(lhs.len()) {
+        toom_3(ret, rhs, lhs);
+    } else {
+        toom_4(ret, rhs, lhs);
+    }
+}
+
+/// multiplies big and small by cutting big in chunks of the size of small
+///
+/// we assume ret is filled with zeros
+fn chunked_mul<T: Digit>(ret: &mut [T], big: &[T], small: &[T]) {
+    let mut buff = vec![T::ZERO; 2 * small.len()];
+    for (i, chunk) in big.chunks(small.len()).enumerate() {
+        let product = &mut buff[..chunk.len() + small.len()];
+        mul(product, chunk, small);
+        add_assign(&mut ret[i * small.len()..], product);
+    }
+}
+
+/// i-th piece of size k of a
+fn piece<T: Digit>(a: &[T], k: usize, i: usize) -> BigInt<T> {
+    let start = (i * k).min(a.len());
+    let end = ((i + 1) * k).min(a.len());
+    BigInt::from(BigUint::from(a[start..end].to_vec()))
+}
+
+/// Division of a by d, when it is known to be exact
+fn div_exact<T: Digit>(mut a: BigInt<T>, d: u32) -> BigInt<T> {
+    // Even part of d: a shift
+    let shift = d.trailing_zeros() as usize;
+    a.uint >>= shift;
+
+    // Odd part: multiplication by the inverse modulo 2^T::NB_BITS, digit by digit
+    let d = d >> shift;
+    if d > 1 {
+        let inverse = match d {
+            3 => T::decomposition_from_u64(0xaaaaaaaaaaaaaaab)[0],
+            5 => T::decomposition_from_u64(0xcccccccccccccccd)[0],
+            _ => unreachable!(),
+        };
+        let d = T::decomposition_from_u32(d)[0];
+        let mut borrow = T::ZERO;
+        for digit in a.uint.val.iter_mut() {
+            let (s, overflow) = digit.overflowing_sub(borrow);
+            let q = (s.to_double() * inverse.to_double()).truncate_upper();
+            *digit = q;
+            borrow = (q.to_double() * d.to_double()).truncate_lower() + T::from(overflow);
+        }
+        debug_assert_eq!(borrow, T::ZERO);
+        a.uint.remove_leading_zeros();
+    }
+
+    if a.uint.is_zero() {
+        a.sign = true;
+    }
+    a
+}
+
+/// Adds the coefficients (all non negative) of the product, of pieces of size k
+fn recompose<T: Digit>(ret: &mut [T], coefficients: &[BigInt<T>], k: usize) {
+    for (i, c) in coefficients.iter().enumerate() {
+        debug_assert!(c.sign || c.uint.is_zero());
+        if !c.uint.is_zero() {
+            add_assign(&mut ret[i * k..], &c.uint.val);
+        }
+    }
+}
+
+/// Splits in 3 pieces for big, 2 for small, and evaluates in 0, 1, -1 and infinity
+///
+/// we assume ret is filled with zeros
+fn toom_32<T: Digit>(ret: &mut [T], big: &[T], small: &[T]) {
+    let k = big.len().div_ceil(3).max(small.len().div_ceil(2));
+    let (a0, a1, a2) = (piece(big, k, 0), piece(big, k, 1), piece(big, k, 2));
+    let (b0, b1) = (piece(small, k, 0), piece(small, k, 1));
+
+    // Evaluations
+    let a02 = &a0 + &a2;
+    let r1 = (&a02 + &a1) * (&b0 + &b1);
+    let rm1 = (a02 - a1) * (&b0 - &b1);
+    let c0 = a0 * b0;
+    let c3 = a2 * b1;
+
+    // Interpolation
+    let c2 = div_exact(&r1 + &rm1, 2) - &c0;
+    let c1 = div_exact(r1 - rm1, 2) - &c3;
+
+    recompose(ret, &[c0, c1, c2, c3], k);
+}
+
+/// Pointwise products of the evaluations of big and small: when both are the
+/// same slice, these are squares
+fn products<T: Digit, const N: usize>(
+    big: &[T],
+    small: &[T],
+    evaluations: impl Fn(&[T]) -> [BigInt<T>; N],
+) -> [BigInt<T>; N] {
+    let a = evaluations(big);
+    if std::ptr::eq(big, small) {
+        a.map(|x| x.square())
+    } else {
+        let mut b = evaluations(small).into_iter();
+        a.map(|x| x * b.next().unwrap())
+    }
+}
+
+/// Splits in 3 pieces of size k and evaluates in 0, 1, -1, -2 and infinity
+fn toom_3_evaluations<T: Digit>(a: &[T], k: usize) -> [BigInt<T>; 5] {
+    let (a0, a1, a2) = (piece(a, k, 0), piece(a, k, 1), piece(a, k, 2));
+    let a02 = &a0 + &a2;
+    let a_1 = &a02 + &a1;
+    let a_m1 = a02 - a1;
+    let a_m2 = ((&a_m1 + &a2) << 1) - &a0;
+    [a0, a_1, a_m1, a_m2, a2]
+}
+
+/// Splits in 3 pieces and evaluates in 0, 1, -1, -2 and infinity, with the
+/// interpolation sequence of Bodrato
+///
+/// we assume ret is filled with zeros
+fn toom_3<T: Digit>(ret: &mut [T], big: &[T], small: &[T]) {
+    let k = big.len().div_ceil(3);
+    let [r0, r1, rm1, rm2, rinf] = products(big, small, |a| toom_3_evaluations(a, k));
+
+    // Interpolation
+    let r3 = div_exact(rm2 - &r1, 3);
+    let r1 = div_exact(r1 - &rm1, 2);
+    let r2 = rm1 - &r0;
+    let r3 = div_exact(&r2 - r3, 2) + (&rinf << 1);
+    let r2 = r2 + &r1 - &rinf;
+    let r1 = r1 - &r3;
+
+    recompose(ret, &[r0, r1, r2, r3, rinf], k);
+}
+
+/// Splits in 4 pieces of size k and evaluates in 0, 1, -1, 2, -2, 1/2 (the
+/// value multiplied by 8) and infinity
+fn toom_4_evaluations<T: Digit>(a: &[T], k: usize) -> [BigInt<T>; 7] {
+    let [a0, a1, a2, a3] = [0, 1, 2, 3].map(|i| piece(a, k, i));
+    let even = &a0 + &a2;
+    let odd = &a1 + &a3;
+    let even_2 = &a0 + (&a2 << 2);
+    let odd_2 = (&a1 << 1) + (&a3 << 3);
+    let half = ((((&a0 << 1) + &a1) << 1) + &a2) << 1;
+    [
+        a0,
+        &even + &odd,
+        even - odd,
+        &even_2 + &odd_2,
+        even_2 - odd_2,
+        half + &a3,
+        a3,
+    ]
+}
+
+/// Splits in 4 pieces and evaluates in 0, 1, -1, 2, -2, 1/2 and infinity
+///
+/// we assume ret is filled with zeros
+fn toom_4<T: Digit>(ret: &mut [T], big: &[T], small: &[T]) {
+    let k = big.len().div_ceil(4);
+    let [c0, r1, rm1, r2, rm2, rh, c6] = products(big, small, |a| toom_4_evaluations(a, k));
+
+    // Interpolation: even coefficients first
+    let e1 = div_exact(&r1 + &rm1, 2) - &c0 - &c6;
+    let o1 = div_exact(r1 - rm1, 2);
+    let e2 = div_exact(&r2 + &rm2, 2) - &c0 - (&c6 << 6);
+    let o2 = div_exact(r2 - rm2, 4);
+    let c4 = div_exact(e2 - (&e1 << 2), 12);
+    let c2 = e1 - &c4;
+
+    // Then odd ones
+    let p = div_exact(rh - (&c0 << 6) - (&c2 << 4) - (&c4 << 2) - &c6, 2);
+    let u = div_exact(&o2 - &o1, 3);
+    let w = div_exact((&o1 << 4) - p, 3);
+    let c3 = div_exact(w - &u, 3);
+    let c5 = div_exact(u - &c3, 5);
+    let c1 = o1 - &c3 - &c5;
+
+    recompose(ret, &[c0, c1, c2, c3, c4, c5, c6], k);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_util::TestRng;
+    use crate::traits::Digit;
+    use crate::BigUint;
+    use typed_test_gen::test_with;
+
+    fn check<T: Digit>(a: &[T], b: &[T], f: fn(&mut [T], &[T], &[T])) {
+        let mut expected = vec![T::ZERO; a.len() + b.len()];
+        super::super::schoolbook_mul(&mut expected, a, b);
+        let mut ret = vec![T::ZERO; a.len() + b.len()];
+        f(&mut ret, a, b);
+        assert_eq!(ret, expected);
+    }
+
+    fn operand<T: Digit>(n: usize, seed: u64) -> Vec<T> {
+        // Deterministic, with long runs of MAX digits to stress the carries
+        let mut ret = TestRng::new(seed).digits(n);
+        for i in (0..n).filter(|i| i % 7 < 3) {
+            ret[i] = T::MAX;
+        }
+        ret
+    }
+
+    #[test_with(u32, u64)]
+    fn toom_variants<T: Digit>() {
+        for (n, m) in [(30, 30), (31, 29), (32, 24), (45, 30), (47, 33), (60, 17)] {
+            let a = operand::<T>(n, 1);
+            let b = operand::<T>(m, 2);
+            check(&a, &b, super::toom_3);
+            check(&a, &b, super::toom_4);
+            check(&a, &b, super::toom_32);
+            check(&a, &b, super::toom);
+
+            let a = vec![T::MAX; n];
+            let b = vec![T::MAX; m];
+            check(&a, &b, super::toom_3);
+            check(&a, &b, super::toom_4);
+            check(&a, &b, super::toom_32);
+        }
+    }
+
+    #[test_with(u32, u64)]
+    fn unbalanced<T: Digit>() {
+        for (n, m) in [(100, 30), (97, 13), (200, 99), (130, 97), (1000, 25)] {
+            let a = operand::<T>(n, 3);
+            let b = operand::<T>(m, 4);
+            check(&a, &b, super::toom);
+            check(&b, &a, super::toom);
+        }
+    }
+
+    #[test_with(u32, u64)]
+    fn squares<T: Digit>() {
+        for n in [30, 31, 45, 60, 97] {
+            for a in [operand::<T>(n, 7), vec![T::MAX; n]] {
+                let mut expected = vec![T::ZERO; 2 * n];
+                super::super::schoolbook_mul(&mut expected, &a, &a.clone());
+                for f in [super::toom_3, super::toom_4, super::toom] {
+                    let mut ret = vec![T::ZERO; 2 * n];
+                    f(&mut ret, &a, &a);
+                    assert_eq!(ret, expected);
+                }
+            }
+        }
+    }
+
+    #[test_with(u32, u64)]
+    fn recursion<T: Digit>() {
+        let a = BigUint::from(operand::<T>(500, 5));
+        let b = BigUint::from(operand::<T>(450, 6));
+        let mut expected = vec![T::ZERO; 950];
+        super::super::schoolbook_mul(&mut expected, &a.val, &b.val);
+        assert_eq!((&a * &b).val, expected);
+        assert_eq!((&a * &a) - (&b * &b), (&a + &b) * (&a - &b));
+    }
+}
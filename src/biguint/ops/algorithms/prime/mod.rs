@@ -0,0 +1,501 @@
+//! prime: primality testing. Candidates first go through trial division by
+//! small primes, then through Miller-Rabin rounds, or through a Baillie-PSW
+//! test (a Miller-Rabin round in base 2 followed by a strong Lucas test).
+//!
+//! Every modular multiplication is done in Montgomery representation, reusing
+//! the machinery of modular exponentiation.
+
+use std::ops::Range;
+use std::sync::OnceLock;
+
+use super::is_perfect_square;
+use super::modpow::{sliding_window_pow, ModularReducer, Montgomery};
+use crate::traits::Digit;
+use crate::BigUint;
+
+mod sieve;
+
+pub(crate) use sieve::find_prime;
+
+/// Trial division is done by all the primes below this bound
+pub(crate) const SMALL_PRIMES_BOUND: u32 = 1024;
+
+/// Primes below `SMALL_PRIMES_BOUND`, by increasing order
+pub(crate) fn small_primes() -> &'static [u32] {
+    static SMALL_PRIMES: OnceLock<Vec<u32>> = OnceLock::new();
+    SMALL_PRIMES.get_or_init(|| {
+        let bound = SMALL_PRIMES_BOUND as usize;
+        let mut is_prime = vec![true; bound];
+        let mut ret = Vec::new();
+        for p in 2..bound {
+            if is_prime[p] {
+                ret.push(p as u32);
+                for multiple in (p * p..bound).step_by(p) {
+                    is_prime[multiple] = false;
+                }
+            }
+        }
+        ret
+    })
+}
+
+/// Remainder of the division of `n` by a small modulus, with native 64 bits
+/// divisions only
+pub(crate) fn rem_u32<T: Digit>(n: &BigUint<T>, m: u32) -> u32 {
+    let m = m as u64;
+    let mut ret = 0u64;
+    for digit in n.val.iter().rev() {
+        let digit: u64 = (*digit).try_into().ok().unwrap();
+        if T::NB_BITS == 64 {
+            ret = ((ret << 32) | (digit >> 32)) % m;
+            ret = ((ret << 32) | (digit & 0xffffffff)) % m;
+        } else {
+            ret = ((ret << 32) | digit) % m;
+        }
+    }
+    ret as u32
+}
+
+/// Small primes, grouped so that their product fits in 32 bits: this way we
+/// only have to compute one remainder of a large integer per group.
+/// Each group is given as its product, and a range of indices in `small_primes()`
+fn small_prime_groups() -> &'static [(u32, Range<usize>)] {
+    static GROUPS: OnceLock<Vec<(u32, Range<usize>)>> = OnceLock::new();
+    GROUPS.get_or_init(|| {
+        let primes = small_primes();
+        let mut ret = Vec::new();
+        let mut start = 0;
+        while start < primes.len() {
+            let mut product = primes[start] as u64;
+            let mut end = start + 1;
+            while end < primes.len() && product * (primes[end] as u64) <= u32::MAX as u64 {
+                product *= primes[end] as u64;
+                end += 1;
+            }
+            ret.push((product as u32, start..end));
+            start = end;
+        }
+        ret
+    })
+}
+
+/// Remainders of `n` modulo each of the small primes
+pub(crate) fn small_prime_residues<T: Digit>(n: &BigUint<T>) -> Vec<u32> {
+    let primes = small_primes();
+    let mut ret = Vec::with_capacity(primes.len());
+    for (product, range) in small_prime_groups() {
+        let r = rem_u32(n, *product);
+        ret.extend(primes[range.clone()].iter().map(|p| r % p));
+    }
+    ret
+}
+
+/// Trial division of `n` by the small primes. Returns None if this was not
+/// enough to decide whether `n` is prime.
+fn trial_division<T: Digit>(n: &BigUint<T>) -> Option<bool> {
+    if n.val.len() == 1 {
+        let small: u64 = n.val[0].try_into().ok().unwrap();
+        if small < SMALL_PRIMES_BOUND as u64 {
+            return Some(small_primes().binary_search(&(small as u32)).is_ok());
+        }
+    }
+
+    let primes = small_primes();
+    for (product, range) in small_prime_groups() {
+        let r = rem_u32(n, *product);
+        if primes[range.clone()].iter().any(|p| r.is_multiple_of(*p)) {
+            return Some(false);
+        }
+    }
+
+    let bound = BigUint::from(SMALL_PRIMES_BOUND as u64 * SMALL_PRIMES_BOUND as u64);
+    (n < &bound).then_some(true)
+}
+
+/// Jacobi symbol (a/n) of two native integers, `n` being odd
+fn jacobi_u64(mut a: u64, mut n: u64) -> i32 {
+    debug_assert!(n & 1 == 1);
+    let mut ret = 1;
+    a %= n;
+    while a != 0 {
+        while a & 1 == 0 {
+            a >>= 1;
+            if n & 7 == 3 || n & 7 == 5 {
+                ret = -ret;
+            }
+        }
+        std::mem::swap(&mut a, &mut n);
+        if a & 3 == 3 && n & 3 == 3 {
+            ret = -ret;
+        }
+        a %= n;
+    }
+    if n == 1 {
+        ret
+    } else {
+        0
+    }
+}
+
+/// Jacobi symbol (a/n), `a` being small and `n` odd
+fn jacobi<T: Digit>(a: i64, n: &BigUint<T>) -> i32 {
+    let n_mod_8 = rem_u32(n, 8);
+    let mut ret = 1;
+    if a < 0 && n_mod_8 & 3 == 3 {
+        ret = -ret;
+    }
+
+    let mut a = a.unsigned_abs();
+    if a == 0 {
+        return (*n == BigUint::new(T::ONE)) as i32;
+    }
+    while a & 1 == 0 {
+        a >>= 1;
+        if n_mod_8 == 3 || n_mod_8 == 5 {
+            ret = -ret;
+        }
+    }
+
+    // Quadratic reciprocity, a and n being both odd
+    if a & 3 == 3 && n_mod_8 & 3 == 3 {
+        ret = -ret;
+    }
+    ret * jacobi_u64(rem_u32(n, a as u32) as u64, a)
+}
+
+/// Small deterministic generator (splitmix64), used to pick the bases of the
+/// Miller-Rabin rounds when no source of randomness is available
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+}
+
+/// Modular arithmetic modulo an odd candidate `n`, in Montgomery representation
+struct PrimalityTester<'a, T: Digit> {
+    n: &'a BigUint<T>,
+    reducer: Montgomery<'a, T>,
+    one: BigUint<T>,
+    minus_one: BigUint<T>,
+}
+
+impl<'a, T: Digit> PrimalityTester<'a, T> {
+    fn new(n: &'a BigUint<T>) -> Self {
+        let reducer = Montgomery::new(n);
+        let one = reducer.one();
+        let minus_one = n - &one;
+        Self {
+            n,
+            reducer,
+            one,
+            minus_one,
+        }
+    }
+
+    /// Representation of a small signed integer
+    fn small(&self, a: i64) -> BigUint<T> {
+        let abs = self.reducer.enter_domain(&BigUint::from(a.unsigned_abs()));
+        if a < 0 && !abs.is_zero() {
+            self.n - &abs
+        } else {
+            abs
+        }
+    }
+
+    fn mul(&self, a: &BigUint<T>, b: &BigUint<T>) -> BigUint<T> {
+        self.reducer.mul(a, b)
+    }
+
+    fn square(&self, a: &BigUint<T>) -> BigUint<T> {
+        self.reducer.square(a)
+    }
+
+    fn add(&self, a: &BigUint<T>, b: &BigUint<T>) -> BigUint<T> {
+        let mut ret = a + b;
+        if &ret >= self.n {
+            ret -= self.n;
+        }
+        ret
+    }
+
+    fn sub(&self, a: &BigUint<T>, b: &BigUint<T>) -> BigUint<T> {
+        if a >= b {
+            a - b
+        } else {
+            (a + self.n) - b
+        }
+    }
+
+    fn half(&self, a: BigUint<T>) -> BigUint<T> {
+        if a.val[0] & T::ONE == T::ONE {
+            (a + self.n) >> 1
+        } else {
+            a >> 1
+        }
+    }
+
+    /// Strong probable prime test in the given base, with `n - 1 = d * 2^s`
+    fn miller_rabin(&self, base: &BigUint<T>, d: &BigUint<T>, s: usize) -> bool {
+        let base = self.reducer.enter_domain(base);
+        let mut x = sliding_window_pow(&self.reducer, &base, d);
+        if x == self.one || x == self.minus_one {
+            return true;
+        }
+        for _ in 1..s {
+            x = self.square(&x);
+            if x == self.minus_one {
+                return true;
+            } else if x == self.one {
+                return false;
+            }
+        }
+        false
+    }
+
+    /// Strong Lucas probable prime test, with the parameters chosen by
+    /// Selfridge's method A
+    fn strong_lucas(&self) -> bool {
+        // The search for the parameters would never end for a square
+        if is_perfect_square(self.n) {
+            return false;
+        }
+
+        // First D in 5, -7, 9, -11, ... with (D/n) = -1
+        let mut d = 5i64;
+        loop {
+            match jacobi(d, self.n) {
+                -1 => break,
+                // A factor of n has been found (n is above the small primes)
+                0 => return false,
+                _ => d = if d > 0 { -d - 2 } else { -d + 2 },
+            }
+        }
+        let (p, q) = (1i64, (1 - d) / 4);
+        let (dd, q) = (self.small(d), self.small(q));
+
+        // n + 1 = k * 2^s
+        let n_plus_one = self.n + T::ONE;
+        let s = n_plus_one.trailing_zeros().unwrap();
+        let k = n_plus_one >> s;
+
+        // Binary computation of U_k, V_k and Q^k
+        let mut u = self.one.clone();
+        let mut v = self.small(p);
+        let mut qk = q.clone();
+        for b in (0..k.nb_bits() - 1).rev() {
+            u = self.mul(&u, &v);
+            v = self.sub(&self.square(&v), &self.add(&qk, &qk));
+            qk = self.square(&qk);
+            if k.bit(b) {
+                // With P = 1: U_{j+1} = (U_j + V_j) / 2, V_{j+1} = (D U_j + V_j) / 2
+                let new_u = self.half(self.add(&u, &v));
+                v = self.half(self.add(&self.mul(&dd, &u), &v));
+                u = new_u;
+                qk = self.mul(&qk, &q);
+            }
+        }
+
+        if u.is_zero() || v.is_zero() {
+            return true;
+        }
+        for _ in 1..s {
+            v = self.sub(&self.square(&v), &self.add(&qk, &qk));
+            if v.is_zero() {
+                return true;
+            }
+            qk = self.square(&qk);
+        }
+        false
+    }
+}
+
+/// Decomposition `n - 1 = d * 2^s`, for an odd `n > 1`
+fn split_n_minus_one<T: Digit>(n: &BigUint<T>) -> (BigUint<T>, usize) {
+    let n_minus_one = n - T::ONE;
+    let s = n_minus_one.trailing_zeros().unwrap();
+    (n_minus_one >> s, s)
+}
+
+/// Miller-Rabin test with `rounds` bases: 2, then bases picked by a
+/// deterministic generator seeded with `n`
+pub(crate) fn is_probable_prime<T: Digit>(n: &BigUint<T>, rounds: usize) -> bool {
+    if let Some(ret) = trial_division(n) {
+        return ret;
+    }
+
+    let tester = PrimalityTester::new(n);
+    let (d, s) = split_n_minus_one(n);
+    if rounds > 0 && !tester.miller_rabin(&BigUint::new(T::TWO), &d, s) {
+        return false;
+    }
+
+    // Other bases are uniform in [2, n - 2]
+    let seed: u64 = n.val[0].try_into().ok().unwrap();
+    let mut generator = SplitMix64(seed ^ n.nb_bits() as u64);
+    let range = n - T::TWO - T::ONE;
+    for _ in 1..rounds {
+        let mut digits = Vec::new();
+        while digits.len() <= n.val.len() {
+            digits.extend(T::decomposition_from_u64(generator.next_u64()));
+        }
+        let base = (BigUint::from(digits) % &range) + T::TWO;
+        if !tester.miller_rabin(&base, &d, s) {
+            return false;
+        }
+    }
+    true
+}
+
+/// Baillie-PSW test: no counterexample is known
+pub(crate) fn is_prime<T: Digit>(n: &BigUint<T>) -> bool {
+    if let Some(ret) = trial_division(n) {
+        return ret;
+    }
+
+    let tester = PrimalityTester::new(n);
+    let (d, s) = split_n_minus_one(n);
+    tester.miller_rabin(&BigUint::new(T::TWO), &d, s) && tester.strong_lucas()
+}
+
+/// Test for safe primes, i.e. primes `n` such that `(n - 1) / 2` is prime as well.
+/// Quick Miller-Rabin rounds in base 2 come first, as most candidates fail them.
+pub(crate) fn is_safe_prime<T: Digit>(n: &BigUint<T>) -> bool {
+    if n.val[0] & T::ONE == T::ZERO {
+        return false;
+    }
+    let half = n >> 1;
+    is_probable_prime(&half, 1) && is_probable_prime(n, 1) && is_prime(&half) && is_prime(n)
+}
+
+/// Smallest prime strictly greater than `n`
+pub(crate) fn next_prime<T: Digit>(n: &BigUint<T>) -> BigUint<T> {
+    find_prime(&(n + T::ONE), None, false).unwrap()
+}
+
+/// Largest prime strictly lower than `n`, if any
+pub(crate) fn prev_prime<T: Digit>(n: &BigUint<T>) -> Option<BigUint<T>> {
+    let three = BigUint::new(T::TWO) + T::ONE;
+    if n <= &three {
+        return (n == &three).then(|| BigUint::new(T::TWO));
+    }
+
+    // Odd candidates only
+    let mut ret = n - T::ONE;
+    if ret.val[0] & T::ONE == T::ZERO {
+        ret -= T::ONE;
+    }
+    while !is_prime(&ret) {
+        ret -= T::TWO;
+    }
+    Some(ret)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use typed_test_gen::test_with;
+
+    #[test]
+    fn jacobi_symbols() {
+        for n in (1u64..200).step_by(2) {
+            for a in -50i64..50 {
+                let expected = jacobi_u64(a.rem_euclid(n as i64) as u64, n);
+                assert_eq!(jacobi(a, &BigUint::<u32>::from(n)), expected);
+            }
+        }
+        assert_eq!(jacobi_u64(1001, 9907), -1);
+        assert_eq!(jacobi_u64(19, 45), 1);
+        assert_eq!(jacobi_u64(30, 35), 0);
+    }
+
+    #[test_with(u32, u64)]
+    fn small_numbers<T: Digit>() {
+        // Sieve of Eratosthenes, up to above the trial division bound
+        let bound = 3 * SMALL_PRIMES_BOUND as usize;
+        let mut sieve = vec![true; bound];
+        sieve[0] = false;
+        sieve[1] = false;
+        for p in 2..bound {
+            for multiple in (2 * p..bound).step_by(p) {
+                sieve[multiple] = false;
+            }
+        }
+
+        for (n, expected) in sieve.into_iter().enumerate() {
+            let n = BigUint::<T>::from(n as u64);
+            assert_eq!(is_prime(&n), expected, "{n}");
+            assert_eq!(is_probable_prime(&n, 5), expected, "{n}");
+        }
+    }
+
+    #[test_with(u32, u64)]
+    fn pseudoprimes<T: Digit>() {
+        // Strong pseudoprimes to base 2, and Carmichael numbers
+        for n in [
+            2047u64,
+            3277,
+            4033,
+            4681,
+            8321,
+            561,
+            41041,
+            825265,
+            3215031751,
+            3825123056546413051,
+        ] {
+            let n = BigUint::<T>::from(n);
+            assert!(!is_prime(&n), "{n}");
+            assert!(!is_probable_prime(&n, 10), "{n}");
+        }
+
+        // Lucas pseudoprimes
+        for n in [5459u64, 5777, 10877, 16109, 18971] {
+            let n = BigUint::<T>::from(n);
+            assert!(PrimalityTester::new(&n).strong_lucas(), "{n}");
+            assert!(!is_prime(&n), "{n}");
+        }
+    }
+
+    #[test_with(u32, u64)]
+    fn large_primes<T: Digit>() {
+        // Mersenne primes and their neighbours
+        for e in [61, 89, 107, 127, 521, 607] {
+            let p = (BigUint::<T>::new(T::ONE) << e) - T::ONE;
+            assert!(is_prime(&p));
+            assert!(is_probable_prime(&p, 10));
+        }
+
+        // Product of two primes
+        let p = (BigUint::<T>::new(T::ONE) << 127) - T::ONE;
+        let q = (BigUint::<T>::new(T::ONE) << 89) - T::ONE;
+        assert!(!is_prime(&(&p * &q)));
+        assert!(!is_probable_prime(&(&p * &q), 10));
+        assert!(!is_prime(&(&p * &p)));
+    }
+
+    #[test_with(u32, u64)]
+    fn neighbours<T: Digit>() {
+        let small = |n: u64| BigUint::<T>::from(n);
+        assert_eq!(next_prime(&small(0)), small(2));
+        assert_eq!(next_prime(&small(2)), small(3));
+        assert_eq!(next_prime(&small(3)), small(5));
+        assert_eq!(next_prime(&small(1048573)), small(1048583));
+        assert_eq!(prev_prime(&small(2)), None);
+        assert_eq!(prev_prime(&small(3)), Some(small(2)));
+        assert_eq!(prev_prime(&small(4)), Some(small(3)));
+        assert_eq!(prev_prime(&small(1048583)), Some(small(1048573)));
+
+        // 2^127 - 1 is prime, as well as 2^127 - 25, but nothing in between
+        let p = (BigUint::<T>::new(T::ONE) << 127) - T::ONE;
+        let q = &p - small(24);
+        assert_eq!(next_prime(&q), p);
+        assert_eq!(prev_prime(&p), Some(q.clone()));
+        assert_eq!(next_prime(&(&q - T::ONE)), q);
+    }
+}
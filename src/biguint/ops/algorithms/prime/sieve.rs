@@ -0,0 +1,132 @@
+//! sieve: search for primes in an interval. Rather than testing every odd
+//! candidate, we compute once the residues of the start of a window modulo the
+//! small primes, and cross out all the candidates of the window having a small
+//! factor, like in the sieve of Eratosthenes.
+
+#[cfg(test)]
+use super::rem_u32;
+use super::{is_prime, is_safe_prime, small_prime_residues, small_primes, SMALL_PRIMES_BOUND};
+use crate::traits::Digit;
+use crate::BigUint;
+
+/// Number of odd candidates in a sieving window
+const SIEVE_LENGTH: usize = 4096;
+
+/// Below this bound, candidates are tested one by one, as the sieve would
+/// cross out the small primes themselves
+const SIEVE_START: u64 = 4 * SMALL_PRIMES_BOUND as u64;
+
+/// Sieving of the odd candidates `start + 2 * j`, for `j < SIEVE_LENGTH`.
+/// Returns the flags of the candidates having no small factor (and, for safe
+/// primes, such that `(candidate - 1) / 2` has no small factor either).
+fn sieve<T: Digit>(start: &BigUint<T>, safe: bool) -> Vec<bool> {
+    debug_assert!(start.val[0] & T::ONE == T::ONE);
+    let mut ret = vec![true; SIEVE_LENGTH];
+    let mut cross_out = |first: usize, step: usize| {
+        for j in (first..SIEVE_LENGTH).step_by(step) {
+            ret[j] = false;
+        }
+    };
+
+    // (candidate - 1) / 2 has to be odd
+    if safe {
+        let first = if start.val[0] & T::TWO == T::ZERO {
+            0
+        } else {
+            1
+        };
+        cross_out(first, 2);
+    }
+
+    let residues = small_prime_residues(start);
+    for (p, r) in small_primes().iter().zip(residues).skip(1) {
+        let (p, r) = (*p as usize, r as usize);
+        let inverse_of_two = p.div_ceil(2);
+
+        // start + 2 * j = 0 (mod p)
+        cross_out(((p - r) * inverse_of_two) % p, p);
+        // start + 2 * j = 1 (mod p)
+        if safe {
+            cross_out(((p + 1 - r) * inverse_of_two) % p, p);
+        }
+    }
+    ret
+}
+
+/// Smallest prime (or safe prime) greater than or equal to `start`. If
+/// `max_bits` is given, returns None if it would have more bits than that.
+pub(crate) fn find_prime<T: Digit>(
+    start: &BigUint<T>,
+    max_bits: Option<usize>,
+    safe: bool,
+) -> Option<BigUint<T>> {
+    let too_big = |n: &BigUint<T>| max_bits.is_some_and(|b| n.nb_bits() > b);
+    let test = |n: &BigUint<T>| if safe { is_safe_prime(n) } else { is_prime(n) };
+
+    let mut candidate = start.clone();
+    let sieve_start = BigUint::from(SIEVE_START);
+    while candidate < sieve_start {
+        if too_big(&candidate) {
+            return None;
+        } else if test(&candidate) {
+            return Some(candidate);
+        }
+        candidate += T::ONE;
+    }
+
+    if candidate.val[0] & T::ONE == T::ZERO {
+        candidate += T::ONE;
+    }
+    loop {
+        if too_big(&candidate) {
+            return None;
+        }
+        for (j, survivor) in sieve(&candidate, safe).into_iter().enumerate() {
+            if !survivor {
+                continue;
+            }
+            let n = &candidate + BigUint::from(2 * j as u64);
+            if too_big(&n) {
+                return None;
+            } else if test(&n) {
+                return Some(n);
+            }
+        }
+        candidate += BigUint::from(2 * SIEVE_LENGTH as u64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use typed_test_gen::test_with;
+
+    #[test_with(u32, u64)]
+    fn sieving<T: Digit>() {
+        let start = (BigUint::<T>::new(T::ONE) << 100) + T::ONE;
+        for safe in [false, true] {
+            for (j, survivor) in sieve(&start, safe).into_iter().enumerate() {
+                let n = &start + BigUint::from(2 * j as u64);
+                let half = &n >> 1;
+                let no_factor = |m: &BigUint<T>| small_primes().iter().all(|p| rem_u32(m, *p) != 0);
+                let expected = no_factor(&n) && (!safe || no_factor(&half));
+                assert_eq!(survivor, expected);
+            }
+        }
+    }
+
+    #[test_with(u32, u64)]
+    fn safe_primes<T: Digit>() {
+        let small = |n: u64| BigUint::<T>::from(n);
+        assert_eq!(find_prime(&small(0), None, true), Some(small(5)));
+        assert_eq!(find_prime(&small(6), None, true), Some(small(7)));
+        assert_eq!(find_prime(&small(8), None, true), Some(small(11)));
+        assert_eq!(find_prime(&small(4000), None, true), Some(small(4007)));
+        assert_eq!(find_prime(&small(5000), None, true), Some(small(5087)));
+        assert_eq!(find_prime(&small(8), Some(3), true), None);
+        assert_eq!(
+            find_prime(&small(1 << 40), None, true),
+            Some(small((1 << 40) + 667))
+        );
+    }
+}
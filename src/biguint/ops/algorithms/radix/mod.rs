@@ -0,0 +1,365 @@
+//! radix: conversion of integers to and from strings in any radix from 2 to 36.
+//!
+//! When the radix is a power of two, its digits are groups of bits, which are
+//! read or written directly from the digits of the integer.
+//!
+//! Otherwise, small integers are converted by repeated divisions by the
+//! largest power of the radix that fits in a digit, r^k. Larger ones are split
+//! around a power r^(k * 2^i) with the Burnikel-Ziegler division, and both
+//! halves are converted recursively, so that the whole conversion costs about
+//! as much as a few multiplications.
+//!
+//! Parsing is the same the other way around: chunks of k digits are
+//! accumulated one digit at a time for short strings, and long strings are
+//! cut at r^(k * 2^i), the two halves being recombined by a multiplication.
+
+use super::add_assign;
+use super::div;
+use super::mul_assign_digit;
+use crate::traits::{Digit, DoubleDigit};
+use crate::BigUint;
+
+// Below this number of digits, conversion is done by divisions by a single digit
+#[cfg(debug_assertions)]
+const TO_RADIX_THRESHOLD: usize = 3;
+
+#[cfg(not(debug_assertions))]
+const TO_RADIX_THRESHOLD: usize = 100;
+
+// Below this number of chunks of characters, parsing is done one chunk at a time
+#[cfg(debug_assertions)]
+const FROM_RADIX_THRESHOLD: usize = 3;
+
+#[cfg(not(debug_assertions))]
+const FROM_RADIX_THRESHOLD: usize = 100;
+
+const CHARACTERS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+/// Largest power of the radix that fits in a digit, and its number of
+/// characters in this radix
+fn chunk<T: Digit>(radix: u32) -> (T, usize) {
+    let max: u64 = T::MAX.try_into().ok().unwrap();
+    let radix = radix as u64;
+    let (mut power, mut k) = (radix, 1);
+    while power <= max / radix {
+        power *= radix;
+        k += 1;
+    }
+    (T::decomposition_from_u64(power)[0], k)
+}
+
+/// The powers (r^k)^(2^i) of the chunk, up to the last one that could be
+/// lower than or equal to n
+fn powers<T: Digit>(n: &BigUint<T>, chunk: T) -> Vec<BigUint<T>> {
+    let mut ret = vec![BigUint::new(chunk)];
+    loop {
+        let last = ret.last().unwrap();
+        if 2 * last.val.len() - 1 > n.val.len() {
+            return ret;
+        }
+        let next = last.square();
+        if next > *n {
+            return ret;
+        }
+        ret.push(next);
+    }
+}
+
+/// Divides a by d in place, and returns the remainder
+fn div_digit<T: Digit>(a: &mut [T], d: T) -> T {
+    let d = d.to_double();
+    let mut r = T::ZERO;
+    for x in a.iter_mut().rev() {
+        let full = (r.to_double() << T::NB_BITS) | x.to_double();
+        *x = (full / d).truncate_upper();
+        r = (full % d).truncate_upper();
+    }
+    r
+}
+
+/// Writes the characters of n in the given radix at the end of out, with
+/// leading zeros to have at least `width` of them.
+///
+/// `powers` have to be the first powers of the chunk, with the next one
+/// (if it were computed) greater than n
+fn write_radix<T: Digit>(
+    n: &BigUint<T>,
+    radix: u32,
+    powers: &[BigUint<T>],
+    width: usize,
+    out: &mut Vec<u8>,
+) {
+    let (chunk, k) = chunk::<T>(radix);
+
+    // Large case: split around the largest power not greater than n
+    if n.val.len() >= TO_RADIX_THRESHOLD {
+        if let Some(i) = powers.iter().rposition(|p| p <= n) {
+            let (q, r) = div(n, &powers[i]).unwrap();
+            let low = k << i;
+            write_radix(&q, radix, &powers[..i], width.saturating_sub(low), out);
+            write_radix(&r, radix, &powers[..i], low, out);
+            return;
+        }
+    }
+
+    // Small case: least significant characters first
+    let mut characters = Vec::with_capacity(n.val.len() * (k + 1));
+    let mut a = n.val.clone();
+    while a.iter().any(|d| *d != T::ZERO) {
+        let mut r: u64 = div_digit(&mut a, chunk).try_into().ok().unwrap();
+        for _ in 0..k {
+            characters.push(CHARACTERS[(r % radix as u64) as usize]);
+            r /= radix as u64;
+        }
+        while a.last() == Some(&T::ZERO) {
+            a.pop();
+        }
+    }
+    while characters.last() == Some(&b'0') {
+        characters.pop();
+    }
+    if characters.len() < width {
+        characters.resize(width, b'0');
+    }
+    out.extend(characters.iter().rev());
+}
+
+/// The `bits` bits of a starting at bit `position`
+fn bits_at<T: Digit>(a: &[T], position: usize, bits: usize) -> usize {
+    let (word, offset) = (position / T::NB_BITS, position % T::NB_BITS);
+    let mut ret = a[word] >> offset;
+    if offset + bits > T::NB_BITS && word + 1 < a.len() {
+        ret |= a[word + 1] << (T::NB_BITS - offset);
+    }
+    let ret: u64 = ret.try_into().ok().unwrap();
+    (ret & ((1 << bits) - 1)) as usize
+}
+
+/// Representation of n in a radix that is a power of two
+fn to_power_of_two<T: Digit>(n: &BigUint<T>, radix: u32) -> String {
+    let bits = radix.trailing_zeros() as usize;
+    let count = n.nb_bits().div_ceil(bits);
+    (0..count)
+        .rev()
+        .map(|i| CHARACTERS[bits_at(&n.val, i * bits, bits)] as char)
+        .collect()
+}
+
+/// Representation of n in the given radix, in lowercase
+pub(crate) fn to_radix<T: Digit>(n: &BigUint<T>, radix: u32) -> String {
+    debug_assert!((2..=36).contains(&radix));
+    if n.is_zero() {
+        return "0".to_string();
+    }
+    if radix.is_power_of_two() {
+        return to_power_of_two(n, radix);
+    }
+
+    // Each character carries at least log2(radix) bits
+    let mut out = Vec::with_capacity(n.nb_bits() / radix.ilog2() as usize + 1);
+    let chunk = chunk::<T>(radix).0;
+    write_radix(n, radix, &powers(n, chunk), 0, &mut out);
+    String::from_utf8(out).unwrap()
+}
+
+/// Value of at most k characters, given as their values in the radix
+fn parse_chunk<T: Digit>(s: &[u8], radix: u32) -> T {
+    let value = s.iter().fold(0u64, |acc, c| acc * radix as u64 + *c as u64);
+    T::decomposition_from_u64(value)[0]
+}
+
+/// Reads the value of a string, given as the values of its characters.
+///
+/// `powers` have to contain the powers of the chunk that are shorter than s
+fn read_radix<T: Digit>(s: &[u8], radix: u32, powers: &[BigUint<T>]) -> BigUint<T> {
+    let (chunk, k) = chunk::<T>(radix);
+
+    // Large case: split around the largest power of the chunk shorter than s
+    if s.len() >= FROM_RADIX_THRESHOLD * k {
+        if let Some(i) = (0..powers.len()).rev().find(|i| k << i < s.len()) {
+            let (high, low) = s.split_at(s.len() - (k << i));
+            let mut ret = read_radix(high, radix, &powers[..i]) * &powers[i];
+            ret += read_radix(low, radix, &powers[..i]);
+            return ret;
+        }
+    }
+
+    // Small case: most significant chunk first, the first one being the shortest
+    let first = match s.len() % k {
+        0 => k.min(s.len()),
+        r => r,
+    };
+    let mut a = vec![parse_chunk::<T>(&s[..first], radix)];
+    a.reserve(s.len() / k + 1);
+    for c in s[first..].chunks(k) {
+        a.push(T::ZERO);
+        mul_assign_digit(&mut a, chunk);
+        if add_assign(&mut a, &[parse_chunk(c, radix)]) {
+            a.push(T::ONE);
+        }
+        if a.last() == Some(&T::ZERO) {
+            a.pop();
+        }
+    }
+    BigUint::from(a)
+}
+
+/// Value of a string in a radix that is a power of two
+fn from_power_of_two<T: Digit>(s: &[u8], radix: u32) -> BigUint<T> {
+    let bits = radix.trailing_zeros() as usize;
+    let mut a = vec![T::ZERO; (s.len() * bits).div_ceil(T::NB_BITS)];
+    for (i, c) in s.iter().rev().enumerate() {
+        let c = T::decomposition_from_u32(*c as u32)[0];
+        let (word, offset) = (i * bits / T::NB_BITS, i * bits % T::NB_BITS);
+        a[word] |= c << offset;
+        if offset + bits > T::NB_BITS {
+            a[word + 1] |= c >> (T::NB_BITS - offset);
+        }
+    }
+    BigUint::from(a)
+}
+
+/// Value of a non-empty string in the given radix, given as the values of its
+/// characters (each lower than the radix)
+pub(crate) fn from_radix<T: Digit>(s: &[u8], radix: u32) -> BigUint<T> {
+    debug_assert!((2..=36).contains(&radix));
+    debug_assert!(!s.is_empty() && s.iter().all(|c| (*c as u32) < radix));
+
+    let mut ret = if radix.is_power_of_two() {
+        from_power_of_two(s, radix)
+    } else {
+        // The powers (r^k)^(2^i) shorter than s
+        let (chunk, k) = chunk::<T>(radix);
+        let mut powers = vec![BigUint::new(chunk)];
+        while k << powers.len() < s.len() {
+            let next = powers.last().unwrap().square();
+            powers.push(next);
+        }
+        read_radix(s, radix, &powers)
+    };
+    ret.remove_leading_zeros();
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_util::TestRng;
+    use crate::traits::{Digit, Pow};
+    use crate::BigUint;
+    use typed_test_gen::test_with;
+
+    /// Quadratic, but obviously correct, conversion
+    fn naive<T: Digit>(n: &BigUint<T>, radix: u32) -> String {
+        let mut characters = vec![];
+        let mut a = n.val.clone();
+        while a.iter().any(|d| *d != T::ZERO) {
+            let r: u64 = super::div_digit(&mut a, T::decomposition_from_u32(radix)[0])
+                .try_into()
+                .ok()
+                .unwrap();
+            characters.push(super::CHARACTERS[r as usize]);
+        }
+        if characters.is_empty() {
+            characters.push(b'0');
+        }
+        characters.reverse();
+        String::from_utf8(characters).unwrap()
+    }
+
+    /// Values of the characters of a string
+    fn values(s: &str) -> Vec<u8> {
+        s.chars().map(|c| c.to_digit(36).unwrap() as u8).collect()
+    }
+
+    /// Some pseudo-random integers of various sizes
+    fn samples<T: Digit>() -> Vec<BigUint<T>> {
+        let mut rng = TestRng::new(1);
+        [1, 2, 3, 5, 8, 13, 40, 100, 250]
+            .iter()
+            .map(|size| BigUint::from(rng.digits(*size)))
+            .collect()
+    }
+
+    #[test_with(u32, u64)]
+    fn chunk<T: Digit>() {
+        let expected = match T::NB_BITS {
+            32 => [(2, 31), (10, 9), (16, 7), (36, 6)],
+            _ => [(2, 63), (10, 19), (16, 15), (36, 12)],
+        };
+        for (radix, k) in expected {
+            assert_eq!(super::chunk::<T>(radix).1, k);
+        }
+    }
+
+    #[test_with(u32, u64)]
+    fn small<T: Digit>() {
+        for n in [0u64, 1, 9, 10, 11, 999_999_999, 1_000_000_000, u64::MAX] {
+            assert_eq!(super::to_radix(&BigUint::<T>::from(n), 10), n.to_string());
+            assert_eq!(
+                super::to_radix(&BigUint::<T>::from(n), 16),
+                format!("{n:x}")
+            );
+            assert_eq!(super::to_radix(&BigUint::<T>::from(n), 8), format!("{n:o}"));
+            assert_eq!(super::to_radix(&BigUint::<T>::from(n), 2), format!("{n:b}"));
+        }
+    }
+
+    #[test_with(u32, u64)]
+    fn powers_of_ten<T: Digit>() {
+        // Many zeros to pad in the lower parts
+        let ten = BigUint::<T>::from(10u32);
+        for k in [18, 19, 20, 37, 38, 76, 150, 301, 600] {
+            let p = ten.pow(k);
+            assert_eq!(super::to_radix(&p, 10), format!("1{}", "0".repeat(k)));
+            assert_eq!(super::to_radix(&(&p - T::ONE), 10), "9".repeat(k));
+            assert_eq!(
+                super::to_radix(&(&p + T::ONE), 10),
+                format!("1{}1", "0".repeat(k - 1))
+            );
+        }
+    }
+
+    #[test_with(u32, u64)]
+    fn against_naive<T: Digit>() {
+        for n in samples::<T>() {
+            for radix in [2, 3, 7, 8, 10, 16, 32, 35, 36] {
+                assert_eq!(super::to_radix(&n, radix), naive(&n, radix));
+            }
+        }
+    }
+
+    #[test_with(u32, u64)]
+    fn parse_small<T: Digit>() {
+        for n in [0u64, 1, 9, 10, 11, 999_999_999, 1_000_000_000, u64::MAX] {
+            let s = n.to_string();
+            assert_eq!(super::from_radix::<T>(&values(&s), 10), BigUint::from(n));
+            let s = format!("{n:x}");
+            assert_eq!(super::from_radix::<T>(&values(&s), 16), BigUint::from(n));
+        }
+        assert_eq!(super::from_radix::<T>(&[0; 24], 10), BigUint::default());
+        assert_eq!(super::from_radix::<T>(&[0; 40], 2), BigUint::default());
+        assert_eq!(
+            super::from_radix::<T>(&values("0000000000000000000000042"), 10),
+            BigUint::from(42u32)
+        );
+    }
+
+    #[test_with(u32, u64)]
+    fn parse_round_trip<T: Digit>() {
+        let ten = BigUint::<T>::from(10u32);
+        for k in [18, 19, 20, 37, 38, 76, 150, 301, 600] {
+            let p = ten.pow(k);
+            for n in [&p - T::ONE, p.clone(), &p + T::ONE] {
+                let s = super::to_radix(&n, 10);
+                assert_eq!(super::from_radix::<T>(&values(&s), 10), n);
+            }
+        }
+
+        for n in samples::<T>() {
+            for radix in [2, 3, 4, 7, 8, 10, 16, 32, 35, 36] {
+                let s = naive(&n, radix);
+                assert_eq!(super::from_radix::<T>(&values(&s), radix), n);
+            }
+        }
+    }
+}
@@ -0,0 +1,240 @@
+//! root: integer square roots (Karatsuba square root) and k-th roots
+//! (Newton's iteration with increasing precision).
+
+use crate::traits::Digit;
+use crate::traits::Pow;
+use crate::traits::RemDiv;
+use crate::BigUint;
+
+// Below this number of bits, roots are computed on native integers
+const NATIVE_THRESHOLD: usize = 128;
+
+fn to_u128<T: Digit>(n: &BigUint<T>) -> u128 {
+    debug_assert!(n.nb_bits() <= 128);
+    let mut ret = 0u128;
+    for (i, d) in n.val.iter().enumerate().take(128 / T::NB_BITS) {
+        let d: u64 = match (*d).try_into() {
+            Ok(d) => d,
+            Err(_) => unreachable!(),
+        };
+        ret |= (d as u128) << (i * T::NB_BITS);
+    }
+    ret
+}
+
+fn from_u128<T: Digit>(n: u128) -> BigUint<T> {
+    BigUint::from(n as u64) + (BigUint::from((n >> 64) as u64) << 64)
+}
+
+/// Returns `n mod 2^b`
+fn low_bits<T: Digit>(n: &BigUint<T>, b: usize) -> BigUint<T> {
+    let mut ret = BigUint::from(n.val[..n.val.len().min(b.div_ceil(T::NB_BITS))].to_vec());
+    if !b.is_multiple_of(T::NB_BITS) && ret.val.len() * T::NB_BITS > b {
+        let last = ret.val.len() - 1;
+        ret.val[last] &= T::MAX >> (T::NB_BITS - b % T::NB_BITS);
+        ret.remove_leading_zeros();
+    }
+    ret
+}
+
+fn isqrt_u128(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+
+    // Newton's iteration, decreasing from an overestimate
+    let mut x = 1u128 << (128 - n.leading_zeros()).div_ceil(2);
+    loop {
+        let y = (x + n / x) / 2;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+/// Current implementation of the square root with remainder: returns `(s, r)`
+/// such that `s^2 <= n = s^2 + r < (s + 1)^2`
+pub(crate) fn sqrt_rem<T: Digit>(n: &BigUint<T>) -> (BigUint<T>, BigUint<T>) {
+    let nb_bits = n.nb_bits();
+    if nb_bits <= NATIVE_THRESHOLD {
+        let n = to_u128(n);
+        let s = isqrt_u128(n);
+        return (from_u128(s), from_u128(n - s * s));
+    }
+
+    // Normalization: the number of bits of n has to be 4k or 4k - 1
+    let t = (4 - nb_bits % 4) % 4 / 2;
+    let normalized = n << (2 * t);
+    let k = (normalized.nb_bits() + 1) / 4;
+
+    // Karatsuba square root: n = (a3 a2 a1 a0) in base 2^k
+    let (s1, r1) = sqrt_rem(&(&normalized >> (2 * k)));
+    let a1 = low_bits(&(&normalized >> k), k);
+    let a0 = low_bits(&normalized, k);
+
+    let (q, u) = ((r1 << k) + a1).rem_div(&(&s1 << 1)).unwrap();
+    let mut s = (s1 << k) + &q;
+    let positive = (u << k) + a0;
+    let negative = q.square();
+    let mut r = if positive >= negative {
+        positive - negative
+    } else {
+        let r = positive + (&s << 1) - T::ONE - negative;
+        s -= T::ONE;
+        r
+    };
+
+    // Back to the actual n
+    if t > 0 {
+        let s0 = low_bits(&s, t);
+        r += (&s0 * &s) << 1;
+        r -= s0.square();
+        r >>= 2 * t;
+        s >>= t;
+    }
+
+    (s, r)
+}
+
+/// Current implementation of the k-th root, rounded down
+pub(crate) fn nth_root<T: Digit>(n: &BigUint<T>, k: usize) -> BigUint<T> {
+    debug_assert!(k > 0);
+    match k {
+        1 => return n.clone(),
+        2 => return sqrt_rem(n).0,
+        _ => (),
+    }
+
+    // Number of bits of the root
+    let nb_bits = n.nb_bits();
+    let root_bits = nb_bits.div_ceil(k);
+    if root_bits <= 1 {
+        return BigUint::from(!n.is_zero() as u64);
+    }
+
+    // Overestimate, from the root of the leading bits of n
+    let x = if root_bits <= 64 {
+        BigUint::new(T::ONE) << root_bits
+    } else {
+        let s = root_bits / 2;
+        (nth_root(&(n >> (k * s)), k) + T::ONE) << s
+    };
+
+    // Newton's iteration, decreasing towards the root
+    let k_big = BigUint::from(k as u64);
+    let mut x = x;
+    loop {
+        let y = ((&x * &BigUint::from(k as u64 - 1)) + n / &x.pow(k - 1)) / &k_big;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+/// Quadratic residues modulo 64, 63, 65 and 11: quickly filters out non squares
+fn may_be_square<T: Digit>(n: &BigUint<T>) -> bool {
+    let residue = |m: u32| -> usize {
+        let r: u64 = match n.rem(&T::decomposition_from_u32(m)[0]).unwrap().try_into() {
+            Ok(r) => r,
+            Err(_) => unreachable!(),
+        };
+        r as usize
+    };
+    let is_residue = |r: usize, m: usize| (0..m).any(|x| x * x % m == r);
+
+    let low: u64 = match n.val[0].try_into() {
+        Ok(d) => d,
+        Err(_) => unreachable!(),
+    };
+    is_residue((low % 64) as usize, 64)
+        && [63, 65, 11]
+            .into_iter()
+            .all(|m| is_residue(residue(m), m as usize))
+}
+
+pub(crate) fn is_perfect_square<T: Digit>(n: &BigUint<T>) -> bool {
+    may_be_square(n) && sqrt_rem(n).1.is_zero()
+}
+
+/// Returns true if `n = x^k` for some `x` and some `k > 1`, with `k` odd if
+/// `odd_only` is set
+pub(crate) fn is_perfect_power<T: Digit>(n: &BigUint<T>, odd_only: bool) -> bool {
+    if n <= &BigUint::new(T::ONE) {
+        return true;
+    }
+    if !odd_only && is_perfect_square(n) {
+        return true;
+    }
+
+    // It's enough to try prime exponents, which have to divide the number of
+    // trailing zeros, if any
+    let trailing_zeros = n.trailing_zeros().unwrap_or(0);
+    let is_prime = |p: usize| {
+        (2..)
+            .take_while(|d| d * d <= p)
+            .all(|d| !p.is_multiple_of(d))
+    };
+    (3..=n.nb_bits())
+        .step_by(2)
+        .filter(|&k| is_prime(k) && (trailing_zeros == 0 || trailing_zeros.is_multiple_of(k)))
+        .any(|k| nth_root(n, k).pow(k) == *n)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::traits::{Digit, Pow};
+    use crate::BigUint;
+    use typed_test_gen::test_with;
+
+    fn check_sqrt<T: Digit>(n: &BigUint<T>) {
+        let (s, r) = super::sqrt_rem(n);
+        assert_eq!(&(&s * &s) + &r, *n);
+        assert!(r <= &s << 1);
+    }
+
+    fn check_root<T: Digit>(n: &BigUint<T>, k: usize) {
+        let x = super::nth_root(n, k);
+        assert!(x.pow(k) <= *n);
+        assert!((x + T::ONE).pow(k) > *n);
+    }
+
+    #[test_with(u32, u64)]
+    fn sqrt<T: Digit>() {
+        for len in [1, 2, 3, 4, 5, 7, 10, 31, 64, 100] {
+            let n = BigUint::<T>::from(vec![T::MAX; len]);
+            check_sqrt(&n);
+            check_sqrt(&(&n - T::ONE));
+            check_sqrt(&(&n >> 3));
+            check_sqrt(&(&n * &n));
+            check_sqrt(&(&n * &n - T::ONE));
+            check_sqrt(&(BigUint::<T>::new(T::ONE) << (len * 37)));
+        }
+    }
+
+    #[test_with(u32, u64)]
+    fn root<T: Digit>() {
+        for len in [1, 2, 5, 20, 60] {
+            let n = BigUint::<T>::from(vec![T::MAX; len]);
+            for k in [3, 4, 5, 7, 31, 100] {
+                check_root(&n, k);
+                check_root(&(&n >> 5), k);
+                let x = super::nth_root(&n, k);
+                check_root(&x.pow(k), k);
+                check_root(&(x.pow(k) - T::ONE), k);
+            }
+        }
+    }
+
+    #[test_with(u32, u64)]
+    fn perfect_powers<T: Digit>() {
+        let n = BigUint::<T>::from(vec![T::MAX; 3]) + T::TWO;
+        assert!(super::is_perfect_square(&n.pow(2)));
+        assert!(!super::is_perfect_square(&(n.pow(2) + T::ONE)));
+        assert!(super::is_perfect_power(&n.pow(5), true));
+        assert!(super::is_perfect_power(&n.pow(6), false));
+        assert!(!super::is_perfect_power(&n.pow(2), true));
+        assert!(!super::is_perfect_power(&(n.pow(7) - T::ONE), false));
+    }
+}
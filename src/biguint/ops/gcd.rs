@@ -0,0 +1,54 @@
+use crate::biguint::ops::extended_gcd;
+use crate::biguint::ops::gcd;
+use crate::traits::Digit;
+use crate::{BigInt, BigUint};
+
+impl<T: Digit> BigUint<T> {
+    /// Returns the greatest common divisor of `self` and `other`.
+    /// By convention, `gcd(n, 0) = n`.
+    ///
+    /// ```
+    /// use twibint::BigUint;
+    ///
+    /// let a = BigUint::<u64>::from(84u64);
+    /// let b = BigUint::<u64>::from(60u64);
+    /// assert_eq!(a.gcd(&b), BigUint::from(12u64));
+    /// ```
+    pub fn gcd(&self, other: &BigUint<T>) -> BigUint<T> {
+        gcd(self, other)
+    }
+
+    /// Returns the least common multiple of `self` and `other`.
+    /// By convention, `lcm(n, 0) = 0`.
+    ///
+    /// ```
+    /// use twibint::BigUint;
+    ///
+    /// let a = BigUint::<u64>::from(84u64);
+    /// let b = BigUint::<u64>::from(60u64);
+    /// assert_eq!(a.lcm(&b), BigUint::from(420u64));
+    /// ```
+    pub fn lcm(&self, other: &BigUint<T>) -> BigUint<T> {
+        if self.is_zero() || other.is_zero() {
+            return BigUint::default();
+        }
+        (self / &self.gcd(other)) * other
+    }
+
+    /// Returns `(g, x, y)` where `g` is the greatest common divisor of `self`
+    /// and `other`, and `x` and `y` are Bézout coefficients such that
+    /// `self * x + other * y = g`.
+    ///
+    /// ```
+    /// use twibint::{BigInt, BigUint};
+    ///
+    /// let a = BigUint::<u64>::from(240u64);
+    /// let b = BigUint::<u64>::from(46u64);
+    /// let (g, x, y) = a.extended_gcd(&b);
+    /// assert_eq!(g, BigUint::from(2u64));
+    /// assert_eq!(BigInt::from(a) * x + BigInt::from(b) * y, BigInt::from(g));
+    /// ```
+    pub fn extended_gcd(&self, other: &BigUint<T>) -> (BigUint<T>, BigInt<T>, BigInt<T>) {
+        extended_gcd(self, other)
+    }
+}
@@ -0,0 +1,126 @@
+use crate::traits::{Digit, Pow};
+use crate::BigUint;
+
+/// Approximation of the base 2 logarithm, for non zero integers of any size
+fn log2_approx<T: Digit>(n: &BigUint<T>) -> f64 {
+    let shift = n.nb_bits().saturating_sub(64);
+    let top = (n >> shift).to_u64_wrapping();
+    (top as f64).log2() + shift as f64
+}
+
+impl<T: Digit> BigUint<T> {
+    /// Returns the base 2 logarithm of `self`, rounded down.
+    ///
+    /// This will panic if `self` is zero.
+    ///
+    /// ```
+    /// use twibint::BigUint;
+    ///
+    /// assert_eq!(BigUint::<u64>::from(1023u64).ilog2(), 9);
+    /// assert_eq!(BigUint::<u64>::from(1024u64).ilog2(), 10);
+    /// ```
+    pub fn ilog2(&self) -> usize {
+        self.checked_ilog2()
+            .expect("The logarithm of zero is not defined")
+    }
+
+    /// Returns the base 2 logarithm of `self`, rounded down, or None if
+    /// `self` is zero.
+    ///
+    /// ```
+    /// use twibint::BigUint;
+    ///
+    /// assert_eq!(BigUint::<u64>::from(1024u64).checked_ilog2(), Some(10));
+    /// assert_eq!(BigUint::<u64>::default().checked_ilog2(), None);
+    /// ```
+    pub fn checked_ilog2(&self) -> Option<usize> {
+        self.nb_bits().checked_sub(1)
+    }
+
+    /// Returns the base 10 logarithm of `self`, rounded down. This is exact,
+    /// whatever the size of `self`.
+    ///
+    /// This will panic if `self` is zero.
+    ///
+    /// ```
+    /// use twibint::BigUint;
+    ///
+    /// assert_eq!(BigUint::<u64>::from(999u64).ilog10(), 2);
+    /// assert_eq!(BigUint::<u64>::from(1000u64).ilog10(), 3);
+    /// ```
+    pub fn ilog10(&self) -> usize {
+        self.checked_ilog10()
+            .expect("The logarithm of zero is not defined")
+    }
+
+    /// Returns the base 10 logarithm of `self`, rounded down, or None if
+    /// `self` is zero.
+    ///
+    /// ```
+    /// use twibint::BigUint;
+    ///
+    /// assert_eq!(BigUint::<u64>::from(1000u64).checked_ilog10(), Some(3));
+    /// assert_eq!(BigUint::<u64>::default().checked_ilog10(), None);
+    /// ```
+    pub fn checked_ilog10(&self) -> Option<usize> {
+        self.checked_ilog(&BigUint::from(10u32))
+    }
+
+    /// Returns the logarithm of `self` in the given base, rounded down.
+    ///
+    /// This will panic if `self` is zero, or if `base` is lower than 2.
+    ///
+    /// ```
+    /// use twibint::BigUint;
+    ///
+    /// let base = BigUint::<u64>::from(7u64);
+    /// assert_eq!(BigUint::<u64>::from(342u64).ilog(&base), 2);
+    /// assert_eq!(BigUint::<u64>::from(343u64).ilog(&base), 3);
+    /// ```
+    pub fn ilog(&self, base: &BigUint<T>) -> usize {
+        assert!(
+            base > &BigUint::new(T::ONE),
+            "The base of a logarithm has to be at least 2"
+        );
+        self.checked_ilog(base)
+            .expect("The logarithm of zero is not defined")
+    }
+
+    /// Returns the logarithm of `self` in the given base, rounded down, or None
+    /// if `self` is zero or `base` lower than 2.
+    ///
+    /// ```
+    /// use twibint::BigUint;
+    ///
+    /// let base = BigUint::<u64>::from(7u64);
+    /// assert_eq!(BigUint::<u64>::from(343u64).checked_ilog(&base), Some(3));
+    /// assert_eq!(BigUint::<u64>::default().checked_ilog(&base), None);
+    /// ```
+    pub fn checked_ilog(&self, base: &BigUint<T>) -> Option<usize> {
+        if self.is_zero() || base <= &BigUint::new(T::ONE) {
+            return None;
+        }
+        if self < base {
+            return Some(0);
+        }
+        if base.count_ones() == 1 {
+            return Some(self.ilog2() / base.ilog2());
+        }
+
+        // Estimation with floating point numbers, then exact correction
+        let mut ret = (log2_approx(self) / log2_approx(base)) as usize;
+        let mut power = base.pow(ret);
+        while &power > self {
+            power = &power / base;
+            ret -= 1;
+        }
+        loop {
+            let next = &power * base;
+            if &next > self {
+                return Some(ret);
+            }
+            power = next;
+            ret += 1;
+        }
+    }
+}
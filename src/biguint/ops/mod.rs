@@ -4,18 +4,39 @@
 pub(crate) mod addsub;
 pub(crate) mod bitwise;
 pub(crate) mod divrem;
+pub(crate) mod gcd;
+pub(crate) mod ilog;
+pub(crate) mod modpow;
 pub(crate) mod mul;
 pub(crate) mod pow;
+pub(crate) mod prime;
+pub(crate) mod root;
 pub(crate) mod shift;
 pub(crate) mod truediv;
 
 mod algorithms;
 pub(crate) use algorithms::add_assign;
 pub(crate) use algorithms::div;
+pub(crate) use algorithms::extended_gcd;
+#[cfg(feature = "rand")]
+pub(crate) use algorithms::find_prime;
+pub(crate) use algorithms::from_radix;
+pub(crate) use algorithms::gcd;
+pub(crate) use algorithms::is_perfect_power;
+pub(crate) use algorithms::is_perfect_square;
+pub(crate) use algorithms::is_prime;
+pub(crate) use algorithms::is_probable_prime;
+pub(crate) use algorithms::modpow;
 pub(crate) use algorithms::mul;
 pub(crate) use algorithms::mul_assign_digit;
+pub(crate) use algorithms::next_prime;
+pub(crate) use algorithms::nth_root;
+pub(crate) use algorithms::prev_prime;
 pub(crate) use algorithms::rsub_assign;
+pub(crate) use algorithms::sqrt_rem;
+pub(crate) use algorithms::square;
 pub(crate) use algorithms::sub_assign;
+pub(crate) use algorithms::to_radix;
 
 #[cfg(test)]
 mod test;
@@ -0,0 +1,23 @@
+use crate::biguint::ops::modpow;
+use crate::traits::{Digit, DivisionResult};
+use crate::BigUint;
+
+impl<T: Digit> BigUint<T> {
+    /// Returns `self` to the power `exp`, modulo `modulus`. \
+    /// Odd moduli use Montgomery multiplication, even ones fall back to a
+    /// division after each multiplication.
+    ///
+    /// This will return an error if `modulus` is zero.
+    ///
+    /// ```
+    /// use twibint::BigUint;
+    ///
+    /// let n = BigUint::<u64>::from(4u64);
+    /// let exp = BigUint::<u64>::from(13u64);
+    /// let modulus = BigUint::<u64>::from(497u64);
+    /// assert_eq!(n.modpow(&exp, &modulus).unwrap(), BigUint::from(445u64));
+    /// ```
+    pub fn modpow(&self, exp: &BigUint<T>, modulus: &BigUint<T>) -> DivisionResult<BigUint<T>> {
+        modpow(self, exp, modulus)
+    }
+}
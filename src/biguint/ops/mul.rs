@@ -1,5 +1,6 @@
 use crate::biguint::ops::mul;
 use crate::biguint::ops::mul_assign_digit;
+use crate::biguint::ops::square;
 use core::iter::Product;
 use core::ops::{Mul, MulAssign};
 
@@ -34,6 +35,38 @@ impl<T: Digit> BigUint<T> {
         self.remove_leading_zeros();
     }
 
+    /// Returns the square of this integer, with a dedicated algorithm that is
+    /// faster than a multiplication
+    ///
+    /// ```
+    /// use twibint::BigUint;
+    ///
+    /// let n = BigUint::<u64>::from(u64::MAX);
+    /// assert_eq!(n.square(), &n * &n);
+    /// ```
+    #[inline]
+    pub fn square(&self) -> BigUint<T> {
+        let mut ret = BigUint::<T>::default().with_capacity(2 * self.val.len() * T::NB_BITS);
+        ret.val.resize(2 * self.val.len(), T::ZERO);
+        square(&mut ret.val, &self.val);
+        ret.remove_leading_zeros();
+        ret
+    }
+
+    /// Replaces this integer by its square
+    ///
+    /// ```
+    /// use twibint::BigUint;
+    ///
+    /// let mut n = BigUint::<u64>::from(12u64);
+    /// n.square_assign();
+    /// assert_eq!(n, BigUint::from(144u64));
+    /// ```
+    #[inline]
+    pub fn square_assign(&mut self) {
+        *self = self.square();
+    }
+
     #[inline]
     pub(crate) fn mul_assign_digit(&mut self, b: T) {
         self.val.push(T::ZERO);
@@ -14,7 +14,7 @@ impl<T: Digit> Pow for BigUint<T> {
             if exp & 1 != 0 {
                 ret *= &base;
             }
-            base = &base * &base;
+            base.square_assign();
             exp >>= 1;
         }
 
@@ -0,0 +1,65 @@
+use crate::biguint::ops::{is_prime, is_probable_prime, next_prime, prev_prime};
+use crate::traits::Digit;
+use crate::BigUint;
+
+impl<T: Digit> BigUint<T> {
+    /// Returns true if `self` is probably prime, after trial division by small
+    /// primes and `rounds` rounds of Miller-Rabin.
+    ///
+    /// The first round is done in base 2, the bases of the other ones are
+    /// chosen deterministically from `self`. A composite number passes each
+    /// of these rounds with a probability lower than 1/4.
+    ///
+    /// ```
+    /// use twibint::BigUint;
+    ///
+    /// let p = (BigUint::<u64>::new(1) << 127) - 1u64;
+    /// assert!(p.is_probable_prime(20));
+    /// assert!(!(&p + 2u64).is_probable_prime(20));
+    /// ```
+    pub fn is_probable_prime(&self, rounds: usize) -> bool {
+        is_probable_prime(self, rounds)
+    }
+
+    /// Returns true if `self` is prime, according to the Baillie-PSW test.
+    ///
+    /// This test is deterministic, and has no known counterexample: it is
+    /// known to be exact below 2^64.
+    ///
+    /// ```
+    /// use twibint::BigUint;
+    ///
+    /// let p = (BigUint::<u64>::new(1) << 521) - 1u64;
+    /// assert!(p.is_prime());
+    /// assert!(!(&p - 2u64).is_prime());
+    /// ```
+    pub fn is_prime(&self) -> bool {
+        is_prime(self)
+    }
+
+    /// Returns the smallest prime strictly greater than `self`.
+    ///
+    /// ```
+    /// use twibint::BigUint;
+    ///
+    /// let n = BigUint::<u64>::from(1000u64);
+    /// assert_eq!(n.next_prime(), BigUint::from(1009u64));
+    /// ```
+    pub fn next_prime(&self) -> BigUint<T> {
+        next_prime(self)
+    }
+
+    /// Returns the largest prime strictly lower than `self`, or None if
+    /// `self` is lower than 3.
+    ///
+    /// ```
+    /// use twibint::BigUint;
+    ///
+    /// let n = BigUint::<u64>::from(1000u64);
+    /// assert_eq!(n.prev_prime(), Some(BigUint::from(997u64)));
+    /// assert_eq!(BigUint::<u64>::from(2u64).prev_prime(), None);
+    /// ```
+    pub fn prev_prime(&self) -> Option<BigUint<T>> {
+        prev_prime(self)
+    }
+}
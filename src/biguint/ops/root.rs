@@ -0,0 +1,73 @@
+use crate::biguint::ops::{is_perfect_power, is_perfect_square, nth_root, sqrt_rem};
+use crate::traits::Digit;
+use crate::BigUint;
+
+impl<T: Digit> BigUint<T> {
+    /// Returns the square root of `self`, rounded down.
+    ///
+    /// ```
+    /// use twibint::BigUint;
+    ///
+    /// let n = BigUint::<u64>::from(99u64);
+    /// assert_eq!(n.isqrt(), BigUint::from(9u64));
+    /// ```
+    pub fn isqrt(&self) -> BigUint<T> {
+        sqrt_rem(self).0
+    }
+
+    /// Returns `(s, r)` where `s` is the square root of `self` rounded down,
+    /// and `r = self - s * s` the remainder.
+    ///
+    /// ```
+    /// use twibint::BigUint;
+    ///
+    /// let n = BigUint::<u64>::from(99u64);
+    /// let (s, r) = n.sqrt_rem();
+    /// assert_eq!(s, BigUint::from(9u64));
+    /// assert_eq!(r, BigUint::from(18u64));
+    /// ```
+    pub fn sqrt_rem(&self) -> (BigUint<T>, BigUint<T>) {
+        sqrt_rem(self)
+    }
+
+    /// Returns the `k`-th root of `self`, rounded down.
+    ///
+    /// This will panic if `k` is zero.
+    ///
+    /// ```
+    /// use twibint::BigUint;
+    ///
+    /// let n = BigUint::<u64>::from(1000u64);
+    /// assert_eq!(n.nth_root(3), BigUint::from(10u64));
+    /// assert_eq!(n.nth_root(4), BigUint::from(5u64));
+    /// ```
+    pub fn nth_root(&self, k: usize) -> BigUint<T> {
+        assert!(k > 0, "The 0th root is not defined");
+        nth_root(self, k)
+    }
+
+    /// Returns true if `self` is the square of an integer.
+    ///
+    /// ```
+    /// use twibint::BigUint;
+    ///
+    /// assert!(BigUint::<u64>::from(144u64).is_perfect_square());
+    /// assert!(!BigUint::<u64>::from(145u64).is_perfect_square());
+    /// ```
+    pub fn is_perfect_square(&self) -> bool {
+        is_perfect_square(self)
+    }
+
+    /// Returns true if `self` is `x^k` for some integers `x` and `k > 1`.
+    /// By convention, this is true for 0 and 1.
+    ///
+    /// ```
+    /// use twibint::BigUint;
+    ///
+    /// assert!(BigUint::<u64>::from(243u64).is_perfect_power());
+    /// assert!(!BigUint::<u64>::from(244u64).is_perfect_power());
+    /// ```
+    pub fn is_perfect_power(&self) -> bool {
+        is_perfect_power(self, false)
+    }
+}
@@ -1,5 +1,6 @@
+use crate::test_util::TestRng;
 use crate::traits::{Digit, Pow, TrueDiv};
-use crate::BigUint;
+use crate::{BigInt, BigUint};
 
 use typed_test_gen::test_with;
 
@@ -499,6 +500,12 @@ fn rem<T: Digit>() {
     let a = BigUint::<T>::from("81129638419329048179758161985792");
     let b = BigUint::<T>::from("571849066607118647405");
     assert_eq!((&a % &b).to_string(), "268965438589694318452");
+
+    // Same number of digits, but smaller numerator
+    let a = BigUint::<T>::from("889428559916540623491963176825");
+    let b = BigUint::<T>::from("906790114395426262226010473049912");
+    assert_eq!(&a % &b, a);
+    assert_eq!(&a / &b, BigUint::<T>::default());
 }
 
 #[test_with(u32, u64)]
@@ -534,6 +541,51 @@ fn sum<T: Digit>() {
     assert_eq!(n2.to_string(), s);
 }
 
+#[test_with(u32, u64)]
+fn truediv_rounding<T: Digit>() {
+    // Quotients of integers exactly representable as f64 are correctly rounded by f64 division
+    let mut rng = TestRng::new(1);
+    for _ in 0..200 {
+        let x = rng.next_u64();
+        let (a, b) = (x >> 11, (x >> 40).max(1));
+        let f = BigUint::<T>::from(a)
+            .truediv(&BigUint::<T>::from(b))
+            .unwrap();
+        assert_eq!(f, a as f64 / b as f64, "{a} / {b}");
+    }
+
+    // Subnormal quotients, and ties to even between them
+    let one = BigUint::<T>::from(1u32);
+    let three = BigUint::<T>::from(3u32);
+    assert_eq!(one.truediv(&(&one << 1074)).unwrap(), f64::from_bits(1));
+    assert_eq!(three.truediv(&(&one << 1075)).unwrap(), f64::from_bits(2));
+    assert_eq!(one.truediv(&(&one << 1075)).unwrap(), 0.0);
+    assert_eq!(
+        one.truediv(&((&one << 1075) - T::ONE)).unwrap(),
+        f64::from_bits(1)
+    );
+    assert_eq!(
+        three.truediv(&(&one << 1024)).unwrap(),
+        f64::from_bits(3 << 50)
+    );
+    assert_eq!(one.truediv(&(&one << 1100)).unwrap(), 0.0);
+
+    // Overflow, and huge operands
+    assert_eq!((&one << 2000).truediv(&three).unwrap(), f64::INFINITY);
+    assert_eq!(
+        (&three << 3000).truediv(&(&one << 2990)).unwrap(),
+        3.0 * 1024.0
+    );
+    assert_eq!(
+        ((&one << 1024) - T::ONE)
+            .truediv(&BigUint::new(T::TWO))
+            .unwrap(),
+        2f64.powi(1023)
+    );
+    assert!(one.truediv(&BigUint::<T>::default()).is_err());
+    assert_eq!(BigUint::<T>::default().truediv(&three).unwrap(), 0.0);
+}
+
 #[test_with(u32, u64)]
 fn truediv<T: Digit>() {
     let n1 = BigUint::<T>::from("123456678890123345567789");
@@ -612,6 +664,31 @@ fn pow<T: Digit>() {
     assert_eq!(n2, BigUint::<T>::from("40564819207303340847894502572032"));
 }
 
+#[test_with(u32, u64)]
+fn square<T: Digit>() {
+    assert_eq!(BigUint::<T>::default().square(), BigUint::<T>::default());
+    assert_eq!(BigUint::<T>::new(T::MAX).square(), {
+        let n = BigUint::<T>::new(T::MAX);
+        &n * &n.clone()
+    });
+
+    // Sizes going through all the squaring algorithms
+    for size in [1, 2, 5, 9, 13, 20, 30, 47, 70, 129, 300] {
+        let n = BigUint::<T>::from(
+            (1..=size as u64)
+                .map(|i| T::decomposition_from_u64(i.wrapping_mul(0x9e3779b97f4a7c15))[0])
+                .collect::<Vec<T>>(),
+        );
+        let expected = &n * &n.clone();
+        assert_eq!(n.square(), expected);
+
+        let mut m = BigUint::<T>::from(vec![T::MAX; size]);
+        let expected = &m * &m.clone();
+        m.square_assign();
+        assert_eq!(m, expected);
+    }
+}
+
 #[test_with(u32, u64)]
 fn long_mul<T: Digit>() {
     let n1 = BigUint::<T>::from(vec![T::ONE; 100]);
@@ -869,3 +946,156 @@ fn test_alignment2() {
             .align_offset(std::mem::align_of::<u64>())
     );
 }
+
+#[test_with(u32, u64)]
+fn gcd<T: Digit>() {
+    let n1 = BigUint::<T>::from(84u32);
+    let n2 = BigUint::<T>::from(60u32);
+    assert_eq!(n1.gcd(&n2), BigUint::<T>::from(12u32));
+    assert_eq!(n2.gcd(&n1), BigUint::<T>::from(12u32));
+    assert_eq!(n1.gcd(&BigUint::<T>::default()), n1);
+    assert_eq!(BigUint::<T>::default().gcd(&n1), n1);
+
+    // gcd(F_m, F_n) = F_gcd(m, n)
+    let mut fib = vec![BigUint::<T>::default(), BigUint::<T>::from(1u32)];
+    for i in 2..=1200 {
+        let next = &fib[i - 1] + &fib[i - 2];
+        fib.push(next);
+    }
+    assert_eq!(fib[1200].gcd(&fib[1000]), fib[200]);
+    assert_eq!(fib[1200].gcd(&fib[900]), fib[300]);
+    assert_eq!(fib[1199].gcd(&fib[1200]), BigUint::<T>::from(1u32));
+}
+
+#[test_with(u32, u64)]
+fn lcm<T: Digit>() {
+    let n1 = BigUint::<T>::from(84u32);
+    let n2 = BigUint::<T>::from(60u32);
+    assert_eq!(n1.lcm(&n2), BigUint::<T>::from(420u32));
+    assert_eq!(n1.lcm(&BigUint::<T>::default()), BigUint::<T>::default());
+
+    let n1 = BigUint::<T>::from(vec![T::MAX; 10]);
+    let n2 = &n1 * &BigUint::<T>::from(3u32);
+    assert_eq!(n1.lcm(&n2), n2);
+}
+
+#[test_with(u32, u64)]
+fn extended_gcd<T: Digit>() {
+    let n1 = BigUint::<T>::from(240u32);
+    let n2 = BigUint::<T>::from(46u32);
+    let (g, x, y) = n1.extended_gcd(&n2);
+    assert_eq!(g, BigUint::<T>::from(2u32));
+    assert_eq!(
+        BigInt::from(n1.clone()) * x + BigInt::from(n2.clone()) * y,
+        BigInt::from(g)
+    );
+
+    let (g, x, y) = n1.extended_gcd(&BigUint::<T>::default());
+    assert_eq!((g, x, y), (n1, BigInt::from(1), BigInt::from(0)));
+
+    let n1 = BigUint::<T>::from(vec![T::MAX; 30]);
+    let n2 = BigUint::<T>::from(vec![T::MAX - T::ONE; 25]);
+    let (g, x, y) = n1.extended_gcd(&n2);
+    assert_eq!(g, n1.gcd(&n2));
+    assert_eq!(BigInt::from(n1) * x + BigInt::from(n2) * y, BigInt::from(g));
+}
+
+#[test_with(u32, u64)]
+fn modpow<T: Digit>() {
+    let n = BigUint::<T>::from(4u32);
+    let exp = BigUint::<T>::from(13u32);
+    let modulus = BigUint::<T>::from(497u32);
+    assert_eq!(
+        n.modpow(&exp, &modulus).unwrap(),
+        BigUint::<T>::from(445u32)
+    );
+    assert_eq!(
+        n.modpow(&BigUint::<T>::default(), &modulus).unwrap(),
+        BigUint::<T>::from(1u32)
+    );
+    assert_eq!(
+        n.modpow(&exp, &BigUint::<T>::from(1u32)).unwrap(),
+        BigUint::<T>::default()
+    );
+    assert!(n.modpow(&exp, &BigUint::<T>::default()).is_err());
+
+    // Even modulus
+    let modulus = BigUint::<T>::from(1000u32);
+    assert_eq!(
+        n.modpow(&exp, &modulus).unwrap(),
+        BigUint::<T>::from(864u32)
+    );
+
+    // Euler's theorem with a big exponent
+    let modulus = BigUint::<T>::from("340282366920938463463374607431768211507");
+    let exp = BigUint::<T>::from(vec![T::MAX; 20]) * (&modulus - T::ONE);
+    let n = BigUint::<T>::from("123456789012345678901234567890");
+    assert_eq!(n.modpow(&exp, &modulus).unwrap(), BigUint::<T>::from(1u32));
+}
+
+#[test_with(u32, u64)]
+fn roots<T: Digit>() {
+    let n = BigUint::<T>::from("152415787532388367501905199875019052100");
+    let s = BigUint::<T>::from("12345678901234567890");
+    assert_eq!(n.isqrt(), s);
+    assert_eq!((&n - T::ONE).isqrt(), &s - T::ONE);
+    assert_eq!(
+        (&n + T::ONE).sqrt_rem(),
+        (s.clone(), BigUint::<T>::from(1u32))
+    );
+    assert!(n.is_perfect_square());
+    assert!(!(&n + T::ONE).is_perfect_square());
+
+    let n = s.pow(7);
+    assert_eq!(n.nth_root(7), s);
+    assert_eq!((&n - T::ONE).nth_root(7), &s - T::ONE);
+    assert_eq!(n.nth_root(1), n);
+    assert!(n.is_perfect_power());
+    assert!(!(&n + T::ONE).is_perfect_power());
+    assert_eq!(BigUint::<T>::default().isqrt(), BigUint::<T>::default());
+    assert_eq!(BigUint::<T>::default().nth_root(5), BigUint::<T>::default());
+}
+
+#[test_with(u32, u64)]
+fn ilog<T: Digit>() {
+    let n = BigUint::<T>::from("1000000000000000000000000000000000000000000");
+    assert_eq!(n.ilog10(), 42);
+    assert_eq!((&n - T::ONE).ilog10(), 41);
+    assert_eq!(n.ilog2(), 139);
+    assert_eq!(n.ilog(&BigUint::<T>::from(1000u32)), 14);
+    assert_eq!(n.ilog(&BigUint::<T>::from(1001u32)), 13);
+    assert_eq!(n.ilog(&BigUint::<T>::from(16u32)), 34);
+    assert_eq!(n.ilog(&n), 1);
+    assert_eq!(n.ilog(&(&n + T::ONE)), 0);
+
+    let base = BigUint::<T>::from("12345678901234567890");
+    let n = base.pow(37);
+    assert_eq!(n.ilog(&base), 37);
+    assert_eq!((&n - T::ONE).ilog(&base), 36);
+
+    assert_eq!(BigUint::<T>::default().checked_ilog2(), None);
+    assert_eq!(BigUint::<T>::default().checked_ilog10(), None);
+    assert_eq!(n.checked_ilog(&BigUint::<T>::new(T::ONE)), None);
+    assert_eq!(BigUint::<T>::new(T::ONE).checked_ilog10(), Some(0));
+
+    assert_eq!(n.trailing_zeros(), Some(37));
+    assert_eq!((n >> 37).trailing_zeros(), Some(0));
+    assert_eq!(BigUint::<T>::from(u64::MAX).count_ones(), 64);
+}
+
+#[test_with(u32, u64)]
+fn primes<T: Digit>() {
+    // 2^89 - 1 is a Mersenne prime
+    let p = (BigUint::<T>::new(T::ONE) << 89) - T::ONE;
+    assert!(p.is_prime());
+    assert!(p.is_probable_prime(10));
+    assert!(!(&p * &p).is_prime());
+    assert_eq!((&p - T::ONE).next_prime(), p);
+    assert_eq!((&p + T::ONE).prev_prime(), Some(p.clone()));
+    assert_eq!(
+        BigUint::<T>::from(561u32).next_prime(),
+        BigUint::from(563u32)
+    );
+    assert!(!BigUint::<T>::default().is_prime());
+    assert!(!BigUint::<T>::new(T::ONE).is_probable_prime(10));
+}
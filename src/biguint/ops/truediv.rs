@@ -1,42 +1,38 @@
+use crate::errors::DivisionError;
+use crate::rounding::{FloatFormat, RoundingMode, F64};
 use crate::traits::DivisionResult;
 use crate::traits::{Digit, RemDiv, TrueDiv};
 use crate::BigUint;
 
-#[cfg(target_endian = "little")]
-impl<T: Digit> TrueDiv<BigUint<T>> for BigUint<T> {
-    fn truediv(&self, n2: &BigUint<T>) -> DivisionResult<f64> {
-        // Compute exponent, biased by 52
-        let mut exponent: i64 = 53i64 - (self.nb_bits() as i64) + (n2.nb_bits() as i64);
-
-        // Actual division
-        let n1 = match exponent {
-            i if i > 0 => self << i.try_into().unwrap(),
-            i if i < 0 => self >> (-i).try_into().unwrap(),
-            _ => self.clone(),
-        };
-        let (mut q, r) = n1.rem_div(n2)?;
-
-        // rounding
-        if r > (n2 >> 1) {
-            q += T::ONE;
+impl<T: Digit> BigUint<T> {
+    /// Rounds self / n2 in the given float format, and returns the bits of
+    /// its encoding (without the sign bit), and whether the rounding is exact
+    pub(crate) fn truediv_bits(
+        &self,
+        n2: &BigUint<T>,
+        format: &FloatFormat,
+        negative: bool,
+        mode: RoundingMode,
+    ) -> DivisionResult<(u64, bool)> {
+        if n2.is_zero() {
+            return Err(DivisionError::DivisionByZero);
         }
 
-        // Get the correct 52 bits of mantissa
-        let extra_bits = q.nb_bits() - 53;
-        q >>= extra_bits;
-        exponent -= extra_bits as i64;
-
-        // Handle overflow or underflow (probably not correct, some answers get mapped to NaNs)
-        if exponent > 52 + 1023 {
-            return Ok(0f64);
-        } else if exponent <= -4096i64 + 52 + 1023 {
-            return Ok(f64::INFINITY);
-        }
+        // Scale the operands so that the quotient has two more bits than the
+        // precision, the remainder then only decides on the rounding of ties
+        let shift = (format.precision + 2 + n2.nb_bits()) as isize - self.nb_bits() as isize;
+        let (q, r) = match shift {
+            s if s >= 0 => (self << s as usize).rem_div(n2)?,
+            s => self.rem_div(&(n2 << (-s) as usize))?,
+        };
+        Ok(format.round(&q, -shift, !r.is_zero(), negative, mode))
+    }
+}
 
-        // Get actual mantissa and exponent biased 1023
-        let mut mantissa: u64 = q.into();
-        mantissa ^= 1u64 << 52;
-        let exponent_u64: u64 = (52 + 1023 - exponent).try_into().unwrap();
-        Ok(f64::from_bits((exponent_u64 << 52) | mantissa))
+#[cfg(target_endian = "little")]
+impl<T: Digit> TrueDiv<BigUint<T>> for BigUint<T> {
+    fn truediv(&self, n2: &BigUint<T>) -> DivisionResult<f64> {
+        let (bits, _) = self.truediv_bits(n2, &F64, false, RoundingMode::NearestEven)?;
+        Ok(f64::from_bits(bits))
     }
 }
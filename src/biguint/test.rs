@@ -1,5 +1,8 @@
-use crate::traits::Digit;
+use crate::errors::{ParseError, TryFromIntError};
+use crate::test_util::TestRng;
+use crate::traits::{Digit, Pow};
 use crate::BigUint;
+use crate::RoundingMode;
 
 use typed_test_gen::test_with;
 
@@ -27,6 +30,112 @@ fn from_str<T: Digit>() {
     assert_eq!(String::from(bg), "1234567891011121314151617181920");
 }
 
+#[test_with(u32, u64)]
+fn str_radix<T: Digit>() {
+    let n = BigUint::<T>::from("1234567891011121314151617181920");
+    assert_eq!(n.to_str_radix(16), "f951a9fd3c158afdff08ab8e0");
+    assert_eq!(
+        BigUint::from_str_radix("F951A9FD3C158AFDFF08AB8E0", 16).unwrap(),
+        n
+    );
+    for radix in 2..=36 {
+        let s = n.to_str_radix(radix);
+        assert_eq!(BigUint::<T>::from_str_radix(&s, radix).unwrap(), n);
+    }
+    assert_eq!(
+        BigUint::<T>::from_str_radix("0000", 2).unwrap(),
+        BigUint::default()
+    );
+    assert_eq!(BigUint::<T>::default().to_str_radix(16), "0");
+
+    let e = BigUint::<T>::from_str_radix("", 10).unwrap_err();
+    assert_eq!(e, ParseError::NoDigits);
+    assert_eq!("".parse::<BigUint<T>>(), Err(ParseError::NoDigits));
+
+    let e = BigUint::<T>::from_str_radix("12z4", 35).unwrap_err();
+    assert_eq!(
+        e,
+        ParseError::UnexpectedCharacter {
+            character: 'z',
+            position: 2
+        }
+    );
+    let e = BigUint::<T>::from_str_radix("12é4", 10).unwrap_err();
+    assert_eq!(
+        e,
+        ParseError::UnexpectedCharacter {
+            character: 'é',
+            position: 2
+        }
+    );
+    let e = "12ü4x".parse::<BigUint<T>>().unwrap_err();
+    assert_eq!(
+        e,
+        ParseError::UnexpectedCharacter {
+            character: 'ü',
+            position: 2
+        }
+    );
+}
+
+#[test_with(u32, u64)]
+#[should_panic]
+fn str_radix_fail<T: Digit>() {
+    BigUint::<T>::default().to_str_radix(37);
+}
+
+#[test_with(u32, u64)]
+fn str_lenient<T: Digit>() {
+    let n = BigUint::<T>::from(0xff_u32);
+    for s in [
+        "255",
+        "0xff",
+        "0XFF",
+        "0o377",
+        "0b1111_1111",
+        "0x_f_f",
+        " +255\n",
+    ] {
+        assert_eq!(BigUint::<T>::from_str_lenient(s).unwrap(), n);
+    }
+    for s in ["0", "000", "0x0", "+0_0", "0b_00"] {
+        assert_eq!(
+            BigUint::<T>::from_str_lenient(s).unwrap(),
+            BigUint::default()
+        );
+    }
+    for s in ["", " \t", " +", "-\n"] {
+        assert_eq!(
+            BigUint::<T>::from_str_lenient(s),
+            Err(ParseError::NoDigits),
+            "{s:?}"
+        );
+    }
+    for (s, character, position) in [
+        ("_1", '_', 0),
+        ("1_", '_', 1),
+        ("1__0", '_', 2),
+        ("0x", 'x', 1),
+        ("0xfg", 'g', 3),
+        ("0b12", '2', 3),
+        ("1 2", ' ', 1),
+        (" -1", '-', 1),
+        ("0x_", '_', 2),
+        ("00255", '0', 0),
+        (" +0_1", '0', 2),
+    ] {
+        let e = BigUint::<T>::from_str_lenient(s).unwrap_err();
+        assert_eq!(
+            e,
+            ParseError::UnexpectedCharacter {
+                character,
+                position
+            },
+            "{s:?}"
+        );
+    }
+}
+
 #[test_with(u32, u64)]
 fn from_u64<T: Digit>() {
     let n = BigUint::from(T::decomposition_from_u64(18446744073709551614u64));
@@ -154,7 +263,7 @@ fn set_bit<T: Digit>() {
 #[test_with(u32, u64)]
 fn binary<T: Digit>() {
     let a = BigUint::<T>::from(vec![T::MAX >> 1, T::MAX >> 1]);
-    let mut s = "0".to_string();
+    let mut s = "".to_string();
     for _ in 0..T::NB_BITS - 1 {
         s.push('1');
     }
@@ -168,7 +277,7 @@ fn binary<T: Digit>() {
 #[test_with(u32, u64)]
 fn hex<T: Digit>() {
     let a = BigUint::<T>::from(vec![T::MAX >> 4, T::MAX >> 4]);
-    let mut s = "0".to_string();
+    let mut s = "".to_string();
     for _ in 0..(T::NB_BITS >> 2) - 1 {
         s.push('f');
     }
@@ -179,6 +288,155 @@ fn hex<T: Digit>() {
     assert_eq!(format!("{:x}", a), s);
 }
 
+#[test_with(u32, u64)]
+fn formatter_flags<T: Digit>() {
+    for n in [0u64, 1, 0xff, 0x1234_5678_9abc_def0, u64::MAX] {
+        let a = BigUint::<T>::from(n);
+        assert_eq!(format!("{:#018x}", a), format!("{:#018x}", n));
+        assert_eq!(format!("{:#X}", a), format!("{:#X}", n));
+        assert_eq!(format!("{:o}", a), format!("{:o}", n));
+        assert_eq!(format!("{:#o}", a), format!("{:#o}", n));
+        assert_eq!(format!("{:#b}", a), format!("{:#b}", n));
+        assert_eq!(format!("{:>30}", a), format!("{:>30}", n));
+        assert_eq!(format!("{:*^30}", a), format!("{:*^30}", n));
+        assert_eq!(format!("{:+025}", a), format!("{:+025}", n));
+        assert_eq!(format!("{:<8x}|", a), format!("{:<8x}|", n));
+    }
+}
+
+#[test_with(u32, u64)]
+fn scientific<T: Digit>() {
+    for n in [
+        0u64,
+        1,
+        9,
+        10,
+        15,
+        25,
+        35,
+        251,
+        999,
+        1250,
+        99_999,
+        123_456_789,
+        u64::MAX,
+    ] {
+        let a = BigUint::<T>::from(n);
+        assert_eq!(format!("{:e}", a), format!("{:e}", n));
+        assert_eq!(format!("{:E}", a), format!("{:E}", n));
+        for precision in 0..22 {
+            assert_eq!(
+                format!("{:.*e}", precision, a),
+                format!("{:.*e}", precision, n)
+            );
+        }
+        assert_eq!(format!("{:>+16.3e}", a), format!("{:>+16.3e}", n));
+        assert_eq!(format!("{:016e}", a), format!("{:016e}", n));
+    }
+
+    // Far beyond the range of a f64
+    let ten = BigUint::<T>::from(10u32);
+    let a = ten.pow(400);
+    assert_eq!(format!("{:e}", a), "1e400");
+    assert_eq!(format!("{:.2e}", &a - T::ONE), "1.00e400");
+    assert_eq!(format!("{:.2E}", (&a >> 1) * T::TWO), "1.00E400");
+    let b = BigUint::<T>::from(1234567u32) * &a + T::ONE;
+    assert_eq!(
+        format!("{:e}", b),
+        format!("1.234567{}1e406", "0".repeat(399))
+    );
+    assert_eq!(format!("{:.3e}", b), "1.235e406");
+}
+
+#[test_with(u32, u64)]
+fn bytes<T: Digit>() {
+    for n in [
+        0u64,
+        1,
+        0xff,
+        0x100,
+        0x1234_5678_9abc,
+        u32::MAX as u64 + 1,
+        u64::MAX,
+    ] {
+        let a = BigUint::<T>::from(n);
+        let le = n.to_le_bytes();
+        let len = le.iter().rposition(|b| *b != 0).unwrap_or(0) + 1;
+        assert_eq!(a.to_bytes_le(), le[..len]);
+        assert_eq!(a.to_bytes_be(), n.to_be_bytes()[8 - len..]);
+        assert_eq!(BigUint::<T>::from_bytes_le(&le), a);
+        assert_eq!(BigUint::<T>::from_bytes_be(&n.to_be_bytes()), a);
+
+        let mut buff = [0xaa; 11];
+        a.write_bytes_be_padded(&mut buff);
+        assert_eq!(buff[..3], [0; 3]);
+        assert_eq!(buff[3..], n.to_be_bytes());
+    }
+    assert_eq!(BigUint::<T>::from_bytes_le(&[]), BigUint::default());
+    assert_eq!(BigUint::<T>::from_bytes_be(&[0; 9]), BigUint::default());
+    BigUint::<T>::default().write_bytes_be_padded(&mut []);
+
+    // Sizes that are not multiples of the digit size
+    let bytes: Vec<u8> = (1..=37).collect();
+    let a = BigUint::<T>::from_bytes_be(&bytes);
+    assert_eq!(a.to_bytes_be(), bytes);
+    assert_eq!(a.to_str_radix(16).len(), 73);
+}
+
+#[test_with(u32, u64)]
+#[should_panic]
+fn bytes_padded_fail<T: Digit>() {
+    BigUint::<T>::from(0x10000u32).write_bytes_be_padded(&mut [0; 2]);
+}
+
+#[test_with(u32, u64)]
+fn primitives<T: Digit>() {
+    assert_eq!(BigUint::<T>::from(200u8), BigUint::from(200u32));
+    assert_eq!(BigUint::<T>::from(60000u16), BigUint::from(60000u32));
+    assert_eq!(
+        BigUint::<T>::from(usize::MAX),
+        BigUint::from(usize::MAX as u64)
+    );
+    let a = BigUint::<T>::from(u128::MAX - 5);
+    assert_eq!(a.to_str_radix(16), format!("{:x}", u128::MAX - 5));
+    assert_eq!(BigUint::<T>::from(0u128), BigUint::default());
+
+    assert_eq!(u128::try_from(&a).unwrap(), u128::MAX - 5);
+    assert_eq!(u8::try_from(&BigUint::<T>::from(255u32)).unwrap(), 255);
+    assert!(u8::try_from(&BigUint::<T>::from(256u32)).is_err());
+    assert_eq!(i8::try_from(&BigUint::<T>::from(127u32)).unwrap(), 127);
+    assert!(i8::try_from(&BigUint::<T>::from(128u32)).is_err());
+    assert_eq!(
+        i64::try_from(&BigUint::<T>::from(i64::MAX as u64)).unwrap(),
+        i64::MAX
+    );
+    assert!(matches!(
+        i64::try_from(&BigUint::<T>::from(u64::MAX)),
+        Err(TryFromIntError::ToInt64Overflow)
+    ));
+    assert!(matches!(
+        u128::try_from(&(&a << 8)),
+        Err(TryFromIntError::ToUint128Overflow)
+    ));
+    assert!(i128::try_from(&a).is_err());
+    assert_eq!(usize::try_from(&BigUint::<T>::from(7u32)).unwrap(), 7);
+    assert_eq!(isize::try_from(&BigUint::<T>::from(7u32)).unwrap(), 7);
+
+    let b = (&a << 64) + BigUint::<T>::from(0x8123_4567_89ab_cdefu64);
+    assert_eq!(b.to_u8_wrapping(), 0xef);
+    assert_eq!(b.to_u16_wrapping(), 0xcdef);
+    assert_eq!(b.to_u32_wrapping(), 0x89ab_cdef);
+    assert_eq!(b.to_u64_wrapping(), 0x8123_4567_89ab_cdef);
+    assert_eq!(b.to_usize_wrapping(), 0x8123_4567_89ab_cdef_u64 as usize);
+    assert_eq!(b.to_i64_wrapping(), 0x8123_4567_89ab_cdef_u64 as i64);
+    assert_eq!(
+        b.to_u128_wrapping(),
+        ((u128::MAX - 5) << 64) | 0x8123_4567_89ab_cdef
+    );
+    assert_eq!(b.to_i128_wrapping(), b.to_u128_wrapping() as i128);
+    assert_eq!(BigUint::<T>::default().to_i32_wrapping(), 0);
+}
+
 #[test_with(u32, u64)]
 fn default<T: Digit>() {
     assert_eq!(BigUint::<T>::default(), BigUint::<T>::new(T::ZERO));
@@ -195,13 +453,82 @@ fn fromstr_fail<T: Digit>() {
     assert!("124test".parse::<BigUint<T>>().is_err());
 }
 
+#[test_with(u32, u64)]
+fn float_rounding<T: Digit>() {
+    // `as` casts from integers are correctly rounded to the nearest, ties to even
+    let mut rng = TestRng::new(1);
+    for _ in 0..200 {
+        let x = ((rng.next_u64() as u128) << 64) | rng.next_u64() as u128;
+        for n in [x, x >> 20, x >> 60, x >> 75, x >> 100] {
+            let a = BigUint::<T>::from(n);
+            assert_eq!(f64::from(&a), n as f64, "{n}");
+            assert_eq!(f32::from(&a), n as f32, "{n}");
+        }
+    }
+    for n in [
+        0u64,
+        1,
+        (1 << 53) + 1,
+        (1 << 53) + 3,
+        (1 << 24) + 1,
+        u64::MAX,
+    ] {
+        let a = BigUint::<T>::from(n);
+        assert_eq!(f64::from(&a), n as f64);
+        assert_eq!(f32::from(&a), n as f32);
+    }
+
+    // Modes and exactness
+    let a = BigUint::<T>::from((1u64 << 54) + 6);
+    let expected = [
+        (RoundingMode::NearestEven, 18014398509481992.0),
+        (RoundingMode::NearestAway, 18014398509481992.0),
+        (RoundingMode::TowardZero, 18014398509481988.0),
+        (RoundingMode::Floor, 18014398509481988.0),
+        (RoundingMode::Ceil, 18014398509481992.0),
+    ];
+    for (mode, f) in expected {
+        assert_eq!(a.to_f64_with_rounding(mode), (f, false), "{mode:?}");
+    }
+    let a = BigUint::<T>::from((1u64 << 54) + 2);
+    assert_eq!(
+        a.to_f64_with_rounding(RoundingMode::NearestEven).0,
+        18014398509481984.0
+    );
+    assert_eq!(
+        a.to_f64_with_rounding(RoundingMode::NearestAway).0,
+        18014398509481988.0
+    );
+    let a = BigUint::<T>::from(1u64 << 60);
+    assert_eq!(
+        a.to_f64_with_rounding(RoundingMode::Ceil),
+        ((1u64 << 60) as f64, true)
+    );
+
+    // Overflow: the tie between f64::MAX and 2^1024 goes to the even 2^1024
+    let one = BigUint::<T>::from(1u32);
+    let tie = (&one << 1024) - (&one << 970);
+    assert_eq!(f64::from(&tie), f64::INFINITY);
+    assert_eq!(f64::from(&(&tie - T::ONE)), f64::MAX);
+    assert_eq!(
+        tie.to_f64_with_rounding(RoundingMode::TowardZero),
+        (f64::MAX, false)
+    );
+    assert_eq!(
+        tie.to_f64_with_rounding(RoundingMode::Floor),
+        (f64::MAX, false)
+    );
+    assert_eq!(f32::from(&(&one << 128)), f32::INFINITY);
+    assert_eq!(f64::from(&(&one << 5000)), f64::INFINITY);
+}
+
 #[test_with(u32, u64)]
 fn f64<T: Digit>() {
     let a = BigUint::<T>::from(u64::MAX);
     let f: f64 = From::from(&a);
     assert_eq!(f, 1.8446744073709552e+19);
 
-    assert_eq!(format!("{:e}", a), format!("{:e}", f));
+    assert_eq!(format!("{:.16e}", a), format!("{:.16e}", f));
 }
 
 #[test_with(u32, u64)]
@@ -1,9 +1,13 @@
 //! errors: declares all error types used throughout the crate
 
-/// UnexpectedCharacterError: might be returned when building
-/// from a string.
-#[derive(Debug)]
-pub struct UnexpectedCharacterError(pub char);
+/// ParseError: might be returned when building from a string. Either the
+/// string has no digits (it is empty, or only a sign), or it has an
+/// unexpected character, held with its position (in bytes) in the string.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseError {
+    NoDigits,
+    UnexpectedCharacter { character: char, position: usize },
+}
 
 /// FromFloatError: might be returned when building
 /// from a float.
@@ -13,6 +17,37 @@ pub enum FromFloatError<T> {
     Negative(T),
 }
 
+/// TryFromIntError: might be returned when converting an integer to a
+/// primitive integer type, signed or unsigned, that cannot hold its value:
+/// the `To...Overflow` variants are for values out of the range of the
+/// target, and `Negative` for negative values with an unsigned target.
+///
+/// ```
+/// use twibint::{BigInt, TryFromIntError};
+///
+/// let n = BigInt::<u64>::from(-200);
+/// assert!(matches!(u8::try_from(&n), Err(TryFromIntError::Negative)));
+/// assert!(matches!(i8::try_from(&n), Err(TryFromIntError::ToInt8Overflow)));
+/// ```
+#[derive(Debug)]
+pub enum TryFromIntError {
+    ToUint8Overflow,
+    ToUint16Overflow,
+    ToUint32Overflow,
+    ToUint64Overflow,
+    ToUint128Overflow,
+    ToUsizeOverflow,
+    ToInt8Overflow,
+    ToInt16Overflow,
+    ToInt32Overflow,
+    ToInt64Overflow,
+    ToInt128Overflow,
+    ToIsizeOverflow,
+    ToDigitOverflow,
+    ToDoubleDigitOverflow,
+    Negative,
+}
+
 /// DivisionByZero: might be returned when calling the division
 /// or rem operations
 #[derive(Debug)]
@@ -34,13 +69,19 @@ impl<T: std::fmt::Display> std::fmt::Display for FromFloatError<T> {
     }
 }
 
-impl std::fmt::Display for UnexpectedCharacterError {
+impl std::fmt::Display for ParseError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(
-            f,
-            "Got an unexpected character when reading string: {}",
-            self.0
-        )
+        match self {
+            Self::NoDigits => write!(f, "Got a string without digits"),
+            Self::UnexpectedCharacter {
+                character,
+                position,
+            } => write!(
+                f,
+                "Got an unexpected character when reading string: {} at position {}",
+                character, position
+            ),
+        }
     }
 }
 
@@ -52,3 +93,21 @@ impl std::fmt::Display for DivisionError {
         }
     }
 }
+
+impl std::fmt::Display for TryFromIntError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Negative => write!(
+                f,
+                "Attempt at converting a negative integer to an unsigned type"
+            ),
+            _ => write!(
+                f,
+                "Attempt at converting an integer out of the range of its target: {:?}",
+                self
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TryFromIntError {}
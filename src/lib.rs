@@ -5,17 +5,27 @@ mod bigint;
 mod biguint;
 mod errors;
 mod export;
+mod rounding;
+#[cfg(test)]
+mod test_util;
 pub mod traits;
 
-pub(crate) use crate::bigfloat::BigFloat;
+pub use crate::bigfloat::BigFloat;
 pub use crate::bigint::BigInt;
 pub use crate::biguint::BigUint;
+pub use crate::errors::ParseError;
+pub use crate::errors::TryFromIntError;
 pub use crate::export::Imported;
+pub use crate::rounding::RoundingMode;
 
 #[cfg(feature = "rand")]
 mod rand;
 #[cfg(feature = "rand")]
-pub use crate::rand::gen_random_biguint;
+pub use crate::rand::{
+    gen_bigint_range, gen_biguint_below, gen_biguint_range, gen_random_bigint,
+    gen_random_bigint_with_rng, gen_random_biguint, gen_random_biguint_with_rng, gen_random_prime,
+    gen_safe_prime, UniformBigInt, UniformBigUint,
+};
 
 #[cfg(feature = "pyo3")]
 mod py_bindings;
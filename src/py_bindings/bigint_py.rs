@@ -1,4 +1,3 @@
-use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 use pyo3::pyclass::CompareOp::*;
 use std::ops::Deref;
@@ -183,15 +182,27 @@ impl BigInt {
         )?)
     }
 
-    /// Python binding to the `**` operation. \
-    /// This will raise an error when called with the modulus argument.
-    pub fn __pow__(&self, other: usize, modulus: Option<usize>) -> PyResult<Self> {
-        if matches!(modulus, Some(_)) {
-            Err(PyErr::new::<PyValueError, _>(
-                "Modulus argument of pow function not supported",
-            ))
+    /// Python binding to the `**` operation, and to the three arguments `pow` function. \
+    /// Without modulus, the exponent must fit in a usize. With a modulus, like for
+    /// Python integers, the result has the sign of the modulus, and a negative
+    /// exponent requires the base to be invertible. \
+    /// This will raise an error if an operand is not compatible with a BigInt,
+    /// or if the modulus is zero.
+    pub fn __pow__(&self, other: &PyAny, modulus: Option<&PyAny>) -> PyResult<Self> {
+        let modulus = match modulus {
+            Some(modulus) => Self::try_from(modulus)?,
+            None => {
+                let exp: usize = other.extract()?;
+                return Ok(Self(crate::BigInt::<u64>::pow(self.as_ref(), exp)));
+            }
+        };
+
+        let exp = Self::try_from(other)?;
+        let ret = crate::BigInt::from(super::modpow(self.as_ref(), &exp, &modulus.uint)?);
+        if modulus.sign || ret.uint.is_zero() {
+            Ok(Self(ret))
         } else {
-            Ok(Self(crate::BigInt::<u64>::pow(self.as_ref(), other)))
+            Ok(Self(ret + modulus.0))
         }
     }
 
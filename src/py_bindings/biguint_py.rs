@@ -1,4 +1,3 @@
-use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 use pyo3::pyclass::CompareOp::*;
 use std::ops::Deref;
@@ -178,16 +177,23 @@ impl BigUint {
         )?)
     }
 
-    /// Python binding to the `**` operation. \
-    /// This will raise an error when called with the modulus argument.
-    pub fn __pow__(&self, other: usize, modulus: Option<usize>) -> PyResult<Self> {
-        if matches!(modulus, Some(_)) {
-            Err(PyErr::new::<PyValueError, _>(
-                "Modulus argument of pow function not supported",
-            ))
-        } else {
-            Ok(Self(crate::BigUint::<u64>::pow(self.as_ref(), other)))
-        }
+    /// Python binding to the `**` operation, and to the three arguments `pow` function. \
+    /// Without modulus, the exponent must fit in a usize. With a modulus, a negative
+    /// exponent requires the base to be invertible. \
+    /// This will raise an error if an operand is not compatible with a BigUint,
+    /// or if the modulus is zero.
+    pub fn __pow__(&self, other: &PyAny, modulus: Option<&PyAny>) -> PyResult<Self> {
+        let modulus = match modulus {
+            Some(modulus) => Self::try_from(modulus)?,
+            None => {
+                let exp: usize = other.extract()?;
+                return Ok(Self(crate::BigUint::<u64>::pow(self.as_ref(), exp)));
+            }
+        };
+
+        let base = crate::BigInt::from(self.0.clone());
+        let exp = super::BigInt::try_from(other)?;
+        Ok(Self(super::modpow(&base, &exp, &modulus)?))
     }
 
     /// Python binding to the `<<` operation
@@ -1,7 +1,7 @@
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 
-use crate::errors::{DivisionError, FromFloatError, UnexpectedCharacterError};
+use crate::errors::{DivisionError, FromFloatError, ParseError};
 
 /// Trait to get the name of an error type as a string, as a static method. \
 /// Must be implemented for any error type that will flow into Python.
@@ -19,10 +19,10 @@ fn py_value_error<ErrorType: ErrorTypeToString>(msg: &str) -> pyo3::PyErr {
     PyErr::new::<PyValueError, _>(string)
 }
 
-/// Get the `UnexpectedCharacterError` name as a string
-impl ErrorTypeToString for UnexpectedCharacterError {
+/// Get the `ParseError` name as a string
+impl ErrorTypeToString for ParseError {
     fn str() -> String {
-        "UnexpectedCharacterError: ".to_string()
+        "ParseError: ".to_string()
     }
 }
 /// Get the `DivisionByZero` name as a string
@@ -38,10 +38,10 @@ impl<T> ErrorTypeToString for FromFloatError<T> {
     }
 }
 
-/// Converts a `UnexpectedCharacterError` to a `ValueError`
-impl From<UnexpectedCharacterError> for pyo3::PyErr {
-    fn from(e: UnexpectedCharacterError) -> Self {
-        py_value_error::<DivisionError>(&e.to_string())
+/// Converts a `ParseError` to a `ValueError`
+impl From<ParseError> for pyo3::PyErr {
+    fn from(e: ParseError) -> Self {
+        py_value_error::<ParseError>(&e.to_string())
     }
 }
 /// Converts a `DivisionByZero` to a `ValueError`
@@ -42,7 +42,7 @@ impl TryFrom<&PyAny> for BigInt {
             Ok(BigInt(int.to_string().as_str().parse()?))
         // Python string
         } else if let Ok(string) = other.downcast::<PyString>() {
-            Ok(BigInt(string.to_str()?.parse()?))
+            Ok(BigInt(crate::BigInt::from_str_lenient(string.to_str()?)?))
         // Rust BigInt
         } else if let Ok(int) = other.extract::<BigInt>() {
             Ok(int)
@@ -97,10 +97,46 @@ impl crate::BigUint<u64> {
     }
 }
 
+/// Modular exponentiation following the semantics of the three arguments `pow`
+/// function of Python: a negative exponent requires the base to be invertible
+/// modulo `modulus`. The result is in the range `[0, modulus)`.
+fn modpow(
+    base: &crate::BigInt<u64>,
+    exp: &crate::BigInt<u64>,
+    modulus: &crate::BigUint<u64>,
+) -> PyResult<crate::BigUint<u64>> {
+    if modulus.is_zero() {
+        return Err(crate::errors::DivisionError::DivisionByZero.into());
+    }
+
+    let signed_modulus = crate::BigInt::from(modulus.clone());
+    let mut base = base % &signed_modulus;
+    if !exp.sign {
+        let (g, inverse, _) = base.extended_gcd(&signed_modulus);
+        if g != crate::BigInt::from(1) {
+            return Err(PyErr::new::<PyValueError, _>(
+                "Base is not invertible for the given modulus",
+            ));
+        }
+        base = inverse % &signed_modulus;
+    }
+
+    Ok(base.uint.modpow(&exp.uint, modulus)?)
+}
+
+/// Random integer of `n` 64 bits digits. If a seed is given, the result is
+/// reproducible.
 #[cfg(feature = "rand")]
 #[pyfunction]
-fn gen_random_biguint(n: usize) -> BigUint {
-    BigUint(crate::gen_random_biguint(n * 64))
+#[pyo3(signature = (n, seed = None))]
+fn gen_random_biguint(n: usize, seed: Option<u64>) -> BigUint {
+    use rand::SeedableRng;
+    BigUint(match seed {
+        Some(seed) => {
+            crate::gen_random_biguint_with_rng(n * 64, &mut rand::rngs::StdRng::seed_from_u64(seed))
+        }
+        None => crate::gen_random_biguint(n * 64),
+    })
 }
 
 #[pyfunction]
@@ -1,17 +1,19 @@
+use crate::biguint::ops::find_prime;
 use crate::traits::Digit;
-use crate::BigUint;
+use crate::{BigInt, BigUint};
 
+use rand::distributions::uniform::{SampleBorrow, SampleUniform, UniformSampler};
 use rand::distributions::Standard;
 use rand::prelude::Distribution;
 use rand::{thread_rng, Rng};
 
-fn gen_n_random_values<T>(n: usize) -> Vec<T>
+fn gen_n_random_values<T, R: Rng + ?Sized>(n: usize, rng: &mut R) -> Vec<T>
 where
     Standard: Distribution<T>,
 {
     let mut ret = Vec::<T>::with_capacity(n);
     for _ in 0..n {
-        ret.push(thread_rng().gen::<T>());
+        ret.push(rng.gen::<T>());
     }
     ret
 }
@@ -20,6 +22,27 @@ where
 ///
 /// The only non random bits is the nth one, which guaranteed to be 1
 pub fn gen_random_biguint<T: Digit>(n: usize) -> BigUint<T>
+where
+    Standard: Distribution<T>,
+{
+    gen_random_biguint_with_rng(n, &mut thread_rng())
+}
+
+/// Generates a random BigUint with n bits, drawn from the given generator.
+/// With a seeded generator, the result is reproducible.
+///
+/// The only non random bits is the nth one, which guaranteed to be 1
+///
+/// ```
+/// use rand::{rngs::StdRng, SeedableRng};
+/// use twibint::{gen_random_biguint_with_rng, BigUint};
+///
+/// let a: BigUint<u64> = gen_random_biguint_with_rng(100, &mut StdRng::seed_from_u64(42));
+/// let b: BigUint<u64> = gen_random_biguint_with_rng(100, &mut StdRng::seed_from_u64(42));
+/// assert_eq!(a, b);
+/// assert_eq!(a.nb_bits(), 100);
+/// ```
+pub fn gen_random_biguint_with_rng<T: Digit, R: Rng + ?Sized>(n: usize, rng: &mut R) -> BigUint<T>
 where
     Standard: Distribution<T>,
 {
@@ -27,7 +50,7 @@ where
         return BigUint::default();
     }
 
-    let mut ret = BigUint::<T>::from(gen_n_random_values::<T>(n / T::NB_BITS + 1));
+    let mut ret = BigUint::<T>::from(gen_n_random_values::<T, R>(n / T::NB_BITS + 1, rng));
     let nb_bits = ret.nb_bits();
 
     if nb_bits == 0 {
@@ -41,8 +64,311 @@ where
     ret
 }
 
+/// Generates a random BigInt with n bits and a random sign
+///
+/// The only non random bits is the nth one, which guaranteed to be 1
+pub fn gen_random_bigint<T: Digit>(n: usize) -> BigInt<T>
+where
+    Standard: Distribution<T>,
+{
+    gen_random_bigint_with_rng(n, &mut thread_rng())
+}
+
+/// Generates a random BigInt with n bits and a random sign, drawn from the
+/// given generator. With a seeded generator, the result is reproducible.
+///
+/// The only non random bits is the nth one, which guaranteed to be 1
+///
+/// ```
+/// use rand::{rngs::StdRng, SeedableRng};
+/// use twibint::{gen_random_bigint_with_rng, BigInt};
+///
+/// let a: BigInt<u64> = gen_random_bigint_with_rng(100, &mut StdRng::seed_from_u64(42));
+/// let b: BigInt<u64> = gen_random_bigint_with_rng(100, &mut StdRng::seed_from_u64(42));
+/// assert_eq!(a, b);
+/// assert_eq!(a.uint.nb_bits(), 100);
+/// ```
+pub fn gen_random_bigint_with_rng<T: Digit, R: Rng + ?Sized>(n: usize, rng: &mut R) -> BigInt<T>
+where
+    Standard: Distribution<T>,
+{
+    let uint = gen_random_biguint_with_rng(n, rng);
+    let sign = rng.gen::<bool>() || uint.is_zero();
+    BigInt { uint, sign }
+}
+
+/// Uniformly random integer with at most n bits
+fn random_bits<T: Digit, R: Rng + ?Sized>(n: usize, rng: &mut R) -> BigUint<T>
+where
+    Standard: Distribution<T>,
+{
+    if n == 0 {
+        return BigUint::default();
+    }
+
+    let mut ret = BigUint::<T>::from(gen_n_random_values::<T, R>(n.div_ceil(T::NB_BITS), rng));
+    let extra_bits = ret.val.len() * T::NB_BITS - n;
+    if extra_bits > 0 {
+        let last = ret.val.last_mut().unwrap();
+        *last = (*last << extra_bits) >> extra_bits;
+    }
+    ret.remove_leading_zeros();
+    ret
+}
+
+/// Generates a uniformly random BigUint in the range `[0, bound)`.
+///
+/// Integers are drawn with as many bits as `bound - 1` until one falls in the
+/// range, so each draw succeeds with a probability of at least 1/2.
+/// This will panic if `bound` is zero.
+///
+/// ```
+/// use twibint::{gen_biguint_below, BigUint};
+///
+/// let bound = BigUint::<u64>::from(1000u64);
+/// let n = gen_biguint_below(&bound, &mut rand::thread_rng());
+/// assert!(n < bound);
+/// ```
+pub fn gen_biguint_below<T: Digit, R: Rng + ?Sized>(bound: &BigUint<T>, rng: &mut R) -> BigUint<T>
+where
+    Standard: Distribution<T>,
+{
+    assert!(!bound.is_zero(), "Cannot sample an empty range");
+    let nb_bits = (bound - T::ONE).nb_bits();
+    loop {
+        let ret = random_bits(nb_bits, rng);
+        if &ret < bound {
+            return ret;
+        }
+    }
+}
+
+/// Generates a uniformly random BigUint in the range `[low, high)`.
+///
+/// This will panic if `low >= high`.
+///
+/// ```
+/// use twibint::{gen_biguint_range, BigUint};
+///
+/// let low = BigUint::<u64>::from(1000u64);
+/// let high = BigUint::<u64>::from(2000u64);
+/// let n = gen_biguint_range(&low, &high, &mut rand::thread_rng());
+/// assert!(low <= n && n < high);
+/// ```
+pub fn gen_biguint_range<T: Digit, R: Rng + ?Sized>(
+    low: &BigUint<T>,
+    high: &BigUint<T>,
+    rng: &mut R,
+) -> BigUint<T>
+where
+    Standard: Distribution<T>,
+{
+    assert!(low < high, "Cannot sample an empty range");
+    gen_biguint_below(&(high - low), rng) + low
+}
+
+/// Generates a uniformly random BigInt in the range `[low, high)`.
+///
+/// This will panic if `low >= high`.
+///
+/// ```
+/// use twibint::{gen_bigint_range, BigInt};
+///
+/// let low = BigInt::<u64>::from(-1000);
+/// let high = BigInt::<u64>::from(1000);
+/// let n = gen_bigint_range(&low, &high, &mut rand::thread_rng());
+/// assert!(low <= n && n < high);
+/// ```
+pub fn gen_bigint_range<T: Digit, R: Rng + ?Sized>(
+    low: &BigInt<T>,
+    high: &BigInt<T>,
+    rng: &mut R,
+) -> BigInt<T>
+where
+    Standard: Distribution<T>,
+{
+    assert!(low < high, "Cannot sample an empty range");
+    BigInt::from(gen_biguint_below(&(high - low).uint, rng)) + low
+}
+
+/// Uniform sampler of BigUint, used by `rng.gen_range(low..high)`
+///
+/// ```
+/// use rand::Rng;
+/// use twibint::BigUint;
+///
+/// let low = BigUint::<u64>::from(1u64) << 100;
+/// let high = BigUint::<u64>::from(1u64) << 101;
+/// let n = rand::thread_rng().gen_range(low.clone()..high.clone());
+/// assert!(low <= n && n < high);
+/// ```
+#[derive(Clone, Debug)]
+pub struct UniformBigUint<T: Digit> {
+    low: BigUint<T>,
+    length: BigUint<T>,
+}
+
+impl<T: Digit> UniformSampler for UniformBigUint<T>
+where
+    Standard: Distribution<T>,
+{
+    type X = BigUint<T>;
+
+    fn new<B1, B2>(low: B1, high: B2) -> Self
+    where
+        B1: SampleBorrow<BigUint<T>> + Sized,
+        B2: SampleBorrow<BigUint<T>> + Sized,
+    {
+        let (low, high) = (low.borrow(), high.borrow());
+        assert!(low < high, "Cannot sample an empty range");
+        UniformBigUint {
+            low: low.clone(),
+            length: high - low,
+        }
+    }
+
+    fn new_inclusive<B1, B2>(low: B1, high: B2) -> Self
+    where
+        B1: SampleBorrow<BigUint<T>> + Sized,
+        B2: SampleBorrow<BigUint<T>> + Sized,
+    {
+        let (low, high) = (low.borrow(), high.borrow());
+        assert!(low <= high, "Cannot sample an empty range");
+        UniformBigUint {
+            low: low.clone(),
+            length: high - low + T::ONE,
+        }
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> BigUint<T> {
+        gen_biguint_below(&self.length, rng) + &self.low
+    }
+}
+
+impl<T: Digit> SampleUniform for BigUint<T>
+where
+    Standard: Distribution<T>,
+{
+    type Sampler = UniformBigUint<T>;
+}
+
+/// Uniform sampler of BigInt, used by `rng.gen_range(low..high)`
+///
+/// ```
+/// use rand::Rng;
+/// use twibint::BigInt;
+///
+/// let low = BigInt::<u64>::from(-1000);
+/// let high = BigInt::<u64>::from(1000);
+/// let n = rand::thread_rng().gen_range(low.clone()..=high.clone());
+/// assert!(low <= n && n <= high);
+/// ```
+#[derive(Clone, Debug)]
+pub struct UniformBigInt<T: Digit> {
+    low: BigInt<T>,
+    length: BigUint<T>,
+}
+
+impl<T: Digit> UniformSampler for UniformBigInt<T>
+where
+    Standard: Distribution<T>,
+{
+    type X = BigInt<T>;
+
+    fn new<B1, B2>(low: B1, high: B2) -> Self
+    where
+        B1: SampleBorrow<BigInt<T>> + Sized,
+        B2: SampleBorrow<BigInt<T>> + Sized,
+    {
+        let (low, high) = (low.borrow(), high.borrow());
+        assert!(low < high, "Cannot sample an empty range");
+        UniformBigInt {
+            low: low.clone(),
+            length: (high - low).uint,
+        }
+    }
+
+    fn new_inclusive<B1, B2>(low: B1, high: B2) -> Self
+    where
+        B1: SampleBorrow<BigInt<T>> + Sized,
+        B2: SampleBorrow<BigInt<T>> + Sized,
+    {
+        let (low, high) = (low.borrow(), high.borrow());
+        assert!(low <= high, "Cannot sample an empty range");
+        UniformBigInt {
+            low: low.clone(),
+            length: (high - low).uint + T::ONE,
+        }
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> BigInt<T> {
+        BigInt::from(gen_biguint_below(&self.length, rng)) + &self.low
+    }
+}
+
+impl<T: Digit> SampleUniform for BigInt<T>
+where
+    Standard: Distribution<T>,
+{
+    type Sampler = UniformBigInt<T>;
+}
+
+/// Generates a random prime with exactly `bits` bits.
+///
+/// A random starting point is drawn, and the first prime above it is searched
+/// for by sieving the following candidates. This will panic if `bits < 2`.
+///
+/// ```
+/// use twibint::{gen_random_prime, BigUint};
+///
+/// let p: BigUint<u64> = gen_random_prime(256, &mut rand::thread_rng());
+/// assert_eq!(p.nb_bits(), 256);
+/// assert!(p.is_prime());
+/// ```
+pub fn gen_random_prime<T: Digit, R: Rng + ?Sized>(bits: usize, rng: &mut R) -> BigUint<T>
+where
+    Standard: Distribution<T>,
+{
+    assert!(bits >= 2, "There is no prime with less than 2 bits");
+    loop {
+        let start = gen_random_biguint_with_rng(bits, rng);
+        if let Some(ret) = find_prime(&start, Some(bits), false) {
+            return ret;
+        }
+    }
+}
+
+/// Generates a random safe prime `p` with exactly `bits` bits, meaning that
+/// `(p - 1) / 2` is prime as well.
+///
+/// Candidates are sieved for small factors of both `p` and `(p - 1) / 2`.
+/// This will panic if `bits < 3`.
+///
+/// ```
+/// use twibint::{gen_safe_prime, BigUint};
+///
+/// let p: BigUint<u64> = gen_safe_prime(64, &mut rand::thread_rng());
+/// assert_eq!(p.nb_bits(), 64);
+/// assert!(p.is_prime());
+/// assert!((p >> 1).is_prime());
+/// ```
+pub fn gen_safe_prime<T: Digit, R: Rng + ?Sized>(bits: usize, rng: &mut R) -> BigUint<T>
+where
+    Standard: Distribution<T>,
+{
+    assert!(bits >= 3, "There is no safe prime with less than 3 bits");
+    loop {
+        let start = gen_random_biguint_with_rng(bits, rng);
+        if let Some(ret) = find_prime(&start, Some(bits), true) {
+            return ret;
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::{BigInt, BigUint};
+    use rand::Rng;
 
     #[test]
     fn test_random_biguint() {
@@ -53,4 +379,111 @@ mod tests {
             assert_eq!(b.nb_bits(), i * 10);
         }
     }
+
+    #[test]
+    fn test_seeded() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        for n in [0, 1, 31, 32, 33, 64, 65, 1000] {
+            let a: BigUint<u32> =
+                super::gen_random_biguint_with_rng(n, &mut StdRng::seed_from_u64(1));
+            let b: BigUint<u32> =
+                super::gen_random_biguint_with_rng(n, &mut StdRng::seed_from_u64(1));
+            assert_eq!(a, b);
+            assert_eq!(a.nb_bits(), n);
+
+            let a: BigInt<u64> =
+                super::gen_random_bigint_with_rng(n, &mut StdRng::seed_from_u64(2));
+            let b: BigInt<u64> =
+                super::gen_random_bigint_with_rng(n, &mut StdRng::seed_from_u64(2));
+            assert_eq!(a, b);
+            assert_eq!(a.uint.nb_bits(), n);
+        }
+
+        // Both signs are produced
+        let mut rng = StdRng::seed_from_u64(3);
+        let signs: Vec<bool> = (0..100)
+            .map(|_| super::gen_random_bigint_with_rng::<u64, _>(10, &mut rng).sign)
+            .collect();
+        assert!(signs.contains(&true) && signs.contains(&false));
+    }
+
+    #[test]
+    fn test_random_below() {
+        let mut rng = rand::thread_rng();
+
+        // Every value of a small range is reached, and only them
+        let bound = BigUint::<u32>::from(10u32);
+        let mut counts = [0usize; 10];
+        for _ in 0..1000 {
+            let n = super::gen_biguint_below(&bound, &mut rng).to_u32_wrapping();
+            counts[n as usize] += 1;
+        }
+        assert!(counts.iter().all(|c| *c > 0));
+
+        for bits in [1, 31, 32, 33, 64, 65, 200] {
+            let bound = BigUint::<u64>::from(1u64) << bits;
+            for _ in 0..100 {
+                assert!(super::gen_biguint_below(&bound, &mut rng) < bound);
+                let bound = &bound + 1u64;
+                assert!(super::gen_biguint_below(&bound, &mut rng) < bound);
+            }
+        }
+        let one = BigUint::<u64>::from(1u64);
+        assert_eq!(super::gen_biguint_below(&one, &mut rng), BigUint::default());
+    }
+
+    #[test]
+    fn test_random_range() {
+        let mut rng = rand::thread_rng();
+
+        let low = BigUint::<u32>::from(u64::MAX);
+        let high = &low + 3u32;
+        for _ in 0..100 {
+            let n = super::gen_biguint_range(&low, &high, &mut rng);
+            assert!(low <= n && n < high);
+            let n = rng.gen_range(low.clone()..=high.clone());
+            assert!(low <= n && n <= high);
+        }
+
+        let low = BigInt::<u64>::from(-3);
+        let high = BigInt::<u64>::from(2);
+        let mut counts = [0usize; 5];
+        for _ in 0..1000 {
+            let n = super::gen_bigint_range(&low, &high, &mut rng);
+            counts[(n + 3).uint.to_usize_wrapping()] += 1;
+            let n = rng.gen_range(low.clone()..high.clone());
+            assert!(low <= n && n < high);
+        }
+        assert!(counts.iter().all(|c| *c > 0));
+    }
+
+    #[test]
+    fn test_random_prime() {
+        let mut rng = rand::thread_rng();
+        for bits in (2..20).chain([32, 33, 64, 65, 100, 200]) {
+            let p = super::gen_random_prime::<u32, _>(bits, &mut rng);
+            assert_eq!(p.nb_bits(), bits);
+            assert!(p.is_prime());
+
+            let p = super::gen_random_prime::<u64, _>(bits, &mut rng);
+            assert_eq!(p.nb_bits(), bits);
+            assert!(p.is_prime());
+        }
+    }
+
+    #[test]
+    fn test_safe_prime() {
+        let mut rng = rand::thread_rng();
+        for bits in (3..20).chain([32, 33, 64, 65, 100]) {
+            let p = super::gen_safe_prime::<u32, _>(bits, &mut rng);
+            assert_eq!(p.nb_bits(), bits);
+            assert!(p.is_prime() && (&p >> 1).is_prime());
+
+            let p = super::gen_safe_prime::<u64, _>(bits, &mut rng);
+            assert_eq!(p.nb_bits(), bits);
+            assert!(p.is_prime() && (&p >> 1).is_prime());
+        }
+    }
 }
@@ -0,0 +1,121 @@
+//! rounding: rounding modes, and correctly rounded conversion of exact values
+//! to the primitive floating point types.
+
+use crate::traits::Digit;
+use crate::BigUint;
+
+/// How to round a value that cannot be represented exactly.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum RoundingMode {
+    /// To the nearest representable value, ties to the even one
+    #[default]
+    NearestEven,
+    /// To the nearest representable value, ties away from zero
+    NearestAway,
+    /// To the representable value closest to zero (truncation)
+    TowardZero,
+    /// To the largest representable value lower than the exact one
+    Floor,
+    /// To the smallest representable value greater than the exact one
+    Ceil,
+}
+
+impl RoundingMode {
+    /// Whether to round the magnitude of a value up, knowing the parity of
+    /// its truncation, if the dropped part is at least half of the last kept
+    /// unit, and if there is anything below that half
+    #[inline]
+    pub(crate) fn round_up(self, negative: bool, odd: bool, half: bool, lower: bool) -> bool {
+        match self {
+            Self::NearestEven => half && (lower || odd),
+            Self::NearestAway => half,
+            Self::TowardZero => false,
+            Self::Floor => negative && (half || lower),
+            Self::Ceil => !negative && (half || lower),
+        }
+    }
+}
+
+/// Description of a binary floating point format
+pub(crate) struct FloatFormat {
+    /// Bits of the significand, implicit bit included
+    pub(crate) precision: usize,
+    /// Exponent of the lowest bit of the subnormal numbers
+    min_exponent: isize,
+    /// Exponent of the highest bit of the largest finite number
+    max_exponent: isize,
+}
+
+pub(crate) const F64: FloatFormat = FloatFormat {
+    precision: 53,
+    min_exponent: -1074,
+    max_exponent: 1023,
+};
+
+pub(crate) const F32: FloatFormat = FloatFormat {
+    precision: 24,
+    min_exponent: -149,
+    max_exponent: 127,
+};
+
+impl FloatFormat {
+    /// Rounds `m * 2^e` in this format, and returns the bits of its encoding
+    /// (without the sign bit), and whether the rounding is exact. `sticky`
+    /// means that the exact value is a bit above `m * 2^e`, which is only
+    /// supported when m has two more bits than the precision.
+    pub(crate) fn round<T: Digit>(
+        &self,
+        m: &BigUint<T>,
+        e: isize,
+        sticky: bool,
+        negative: bool,
+        mode: RoundingMode,
+    ) -> (u64, bool) {
+        if m.is_zero() {
+            return (0, !sticky);
+        }
+        let p = self.precision;
+        debug_assert!(!sticky || m.nb_bits() >= p + 2);
+
+        // Exponent of the last bit that will be kept
+        let top = e + m.nb_bits() as isize - 1;
+        let mut unit = (top - p as isize + 1).max(self.min_exponent);
+
+        let (mut q, exact) = match unit - e {
+            shift if shift <= 0 => (m.to_u64_wrapping() << -shift, !sticky),
+            shift => {
+                let shift = shift as usize;
+                let q = (m >> shift).to_u64_wrapping();
+                let half = m.bit(shift - 1);
+                let lower = sticky || m.trailing_zeros().unwrap() < shift - 1;
+                let up = mode.round_up(negative, q & 1 == 1, half, lower);
+                (q + up as u64, !half && !lower)
+            }
+        };
+        if q == 1 << p {
+            q >>= 1;
+            unit += 1;
+        }
+
+        // Overflow: infinity, or the largest finite number when rounding toward zero
+        if unit + p as isize - 1 > self.max_exponent {
+            let largest = match mode {
+                RoundingMode::NearestEven | RoundingMode::NearestAway => false,
+                RoundingMode::TowardZero => true,
+                RoundingMode::Floor => !negative,
+                RoundingMode::Ceil => negative,
+            };
+            let infinity = ((self.max_exponent * 2 + 1) as u64) << (p - 1);
+            return (infinity - largest as u64, false);
+        }
+
+        // Subnormal numbers have no implicit bit, and a zero exponent
+        match q >> (p - 1) {
+            0 => (q, exact),
+            _ => {
+                let biased = (unit - self.min_exponent + 1) as u64;
+                ((biased << (p - 1)) | (q ^ (1 << (p - 1))), exact)
+            }
+        }
+    }
+}
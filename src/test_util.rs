@@ -0,0 +1,28 @@
+//! test_util: helpers shared by the tests
+
+use crate::traits::Digit;
+
+/// Deterministic pseudo-random numbers for the tests, from a linear
+/// congruential generator
+pub(crate) struct TestRng(u64);
+
+impl TestRng {
+    pub(crate) fn new(seed: u64) -> TestRng {
+        TestRng(seed)
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        self.0
+    }
+
+    /// `n` pseudo-random digits
+    pub(crate) fn digits<T: Digit>(&mut self, n: usize) -> Vec<T> {
+        (0..n)
+            .map(|_| T::decomposition_from_u64(self.next_u64())[0])
+            .collect()
+    }
+}
@@ -100,6 +100,7 @@ where
     fn overflowing_sub(self, other: Self) -> (Self, bool);
     fn leading_zeros(self) -> u32;
     fn trailing_zeros(self) -> u32;
+    fn count_ones(self) -> u32;
     fn decomposition_from_u32(n: u32) -> Vec<Self>;
     fn decomposition_from_u64(n: u64) -> Vec<Self>;
     fn write_bytes(self, buff: &mut [u8]);
@@ -173,6 +174,10 @@ impl Digit for u32 {
         self.trailing_zeros()
     }
     #[inline]
+    fn count_ones(self) -> u32 {
+        self.count_ones()
+    }
+    #[inline]
     fn decomposition_from_u32(n: u32) -> Vec<Self> {
         vec![n]
     }
@@ -256,6 +261,10 @@ impl Digit for u64 {
         self.trailing_zeros()
     }
     #[inline]
+    fn count_ones(self) -> u32 {
+        self.count_ones()
+    }
+    #[inline]
     fn decomposition_from_u32(n: u32) -> Vec<Self> {
         vec![n as u64]
     }
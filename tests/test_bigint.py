@@ -50,6 +50,12 @@ def test_pow():
 	assert BigInt(A) ** 2 == BigInt(A ** 2)
 	assert BigInt(B) ** 2 == BigInt(B ** 2)
 
+def test_pow_modulus():
+	assert pow(BigInt(A), B, 1000000007) == BigInt(pow(A, B, 1000000007))
+	assert pow(BigInt(A), B, -1000000007) == BigInt(pow(A, B, -1000000007))
+	assert pow(BigInt(-A), 3, 2 ** 64) == BigInt(pow(-A, 3, 2 ** 64))
+	assert pow(BigInt(A), -1, 1000000007) == BigInt(pow(A, -1, 1000000007))
+
 def test_shr():
 	assert (BigInt(A) >> 10 == BigInt(A >> 10))
 	assert (BigInt(B) >> 10 == BigInt(B >> 10))
@@ -51,6 +51,11 @@ def test_pow():
 	assert BigUint(A) ** 2 == BigUint(A ** 2)
 	assert BigUint(B) ** 2 == BigUint(B ** 2)
 
+def test_pow_modulus():
+	assert pow(BigUint(A), B, 1000000007) == BigUint(pow(A, B, 1000000007))
+	assert pow(BigUint(A), 3, 2 ** 64) == BigUint(pow(A, 3, 2 ** 64))
+	assert pow(BigUint(A), -1, 1000000007) == BigUint(pow(A, -1, 1000000007))
+
 def test_shr():
 	assert (BigUint(A) >> 10 == BigUint(A >> 10))
 def test_shl():
@@ -113,6 +118,12 @@ def test_rand():
 	C = gen_random_biguint(100)
 	assert True
 
+def test_rand_seed():
+	C = gen_random_biguint(100, 12345)
+	D = gen_random_biguint(100, seed=12345)
+	assert C == D
+	assert C != gen_random_biguint(100, 54321)
+
 def test_read_write():
 	C = gen_random_biguint(100)
 	C.write_to_file("test_file_py_biguint")